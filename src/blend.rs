@@ -1,6 +1,7 @@
 
 /** Porter-Duff Compositing Operators & Blending Modes
 
+```text
       Simple alpha compositing: co = Cs x αs + Cb x αb x (1 - αs)
         resultant alpha of the composite: αo = αs + αb x (1 - αs)
 
@@ -45,7 +46,8 @@
 
     https://en.wikipedia.org/wiki/Blend_modes
 
-    https://www.w3.org/TR/compositing-1 */
+    https://www.w3.org/TR/compositing-1
+``` */
 
 #[derive(Clone, Copy, Debug, PartialEq)] pub enum BlendMode {
     //  (Alpha) Porter-Duff Compositing Operators:
@@ -96,6 +98,24 @@
     /** Creates a color with the luminosity of the source color and
         the hue and saturation of the backdrop color.
         This produces an inverse effect to that of the Color mode. */ Luminosity,
+    //  Note: Hue/Saturation/Color/Luminosity above are in HSY (W3C, luma & chroma) model.
+
+    //  Non-separable variants in other color models, as Krita does:
+    //  https://docs.krita.org/en/reference_manual/blending_modes/hsx.html
+    /** Hue of the source with saturation and lightness of the backdrop in HSL. */ HueHSL,
+    /** Saturation of the source with hue and lightness of the backdrop in HSL. */
+    SaturationHSL,
+    /** Hue and saturation of the source with lightness of the backdrop in HSL. */ ColorHSL,
+    /** Lightness of the source with hue and saturation of the backdrop in HSL. */ Lightness,
+    /** Hue of the source with saturation and value of the backdrop in HSV. */ HueHSV,
+    /** Saturation of the source with hue and value of the backdrop in HSV. */ SaturationHSV,
+    /** Hue and saturation of the source with value of the backdrop in HSV. */ ColorHSV,
+    /** Value of the source with hue and saturation of the backdrop in HSV. */ Value,
+    /** Hue of the source with saturation and intensity of the backdrop in HSI. */ HueHSI,
+    /** Saturation of the source with hue and intensity of the backdrop in HSI. */
+    SaturationHSI,
+    /** Hue and saturation of the source with intensity of the backdrop in HSI. */ ColorHSI,
+    /** Intensity of the source with hue and saturation of the backdrop in HSI. */ Intensity,
 
     //  ...
 }
@@ -103,19 +123,81 @@
 pub type CompOp = BlendMode;
 pub use crate::color::RGBA;
 
-/* impl RGBA<u8> {
+/// Span compositor: composite a span of source pixels onto the destination ones in place.
+pub type SpanFn = Box<dyn Fn(&mut [RGBA<u8>], &[RGBA<u8>]) + Send + Sync>;
+
+/// x * y / 255 rounded to the nearest, without division, exact for x, y <= 255.
+#[inline] pub fn mul255(x: u32, y: u32) -> u32 { let t = x * y + 128; (t + (t >> 8)) >> 8 }
+
+impl BlendMode {
+    /// Whether it is an (alpha) Porter-Duff compositing operator.
+    #[inline] pub fn is_porter_duff(self) -> bool { (self as u8) <= (Self::Lighter as u8) }
+
+    /// Factors (Fa, Fb) of the Porter-Duff operator in the range of [0, 255].
+    fn factors(self, sa: u32, da: u32) -> (u32, u32) {     use BlendMode::*;
+        match self {
+            Clear   => (0, 0),   Copy    => (255, 0),       Dest    => (0, 255),
+            SrcOver => (255, 255 - sa), DstOver => (255 - da, 255),
+            SrcIn   => (da, 0),  SrcOut  => (255 - da, 0),  SrcAtop => (da, 255 - sa),
+            DstIn   => (0, sa),  DstOut  => (0, 255 - sa),  DstAtop => (255 - da, sa),
+            XOR     => (255 - da, 255 - sa),    Lighter => (255, 255),  _ => unreachable!(),
+        }
+    }
+
+    /** Composite a span of NON-premultiplied source pixels onto the destination ones in place,
+        with source alpha scaled by the opacity. Porter-Duff operators are in integer math,
+        while blending modes go through `RGBA<f32>::compose`.
+    ```
+    use ugl_rs::blend::{BlendMode, RGBA};
+    let mut dst = [RGBA::new(0, 0, 255, 255); 2];
+    BlendMode::SrcOver.composite_span(&mut dst, &[RGBA::new(255, 0, 0, 255); 2], 128);
+    assert_eq!(dst, [RGBA::new(128, 0, 127, 255); 2]);
+    BlendMode::DstOut .composite_span(&mut dst, &[RGBA::new(0, 0, 0, 255), RGBA::zeroed()], 255);
+    assert_eq!(dst, [RGBA::zeroed(), RGBA::new(128, 0, 127, 255)]);
+    BlendMode::Multiply.composite_span(&mut dst, &[RGBA::new(255, 128, 0, 255); 2], 255);
+    assert_eq!(dst[1], RGBA::new(128, 0, 0, 255));
+    let mut dst = [RGBA::new(0, 0, 255, 255), RGBA::new(0, 0, 200, 100)];
+    BlendMode::Lighter.composite_span(&mut dst, &[RGBA::new(255, 0, 0, 255); 2], 255);
+    assert_eq!(dst, [RGBA::new(255, 0, 255, 255), RGBA::new(255, 0, 78, 255)]);
+    ``` */
+    pub fn composite_span(self, dst: &mut [RGBA<u8>], src: &[RGBA<u8>], opacity: u8) {
+        let opacity = opacity as u32;
+        if !self.is_porter_duff() {
+            let opacity = opacity as f32 / 255.;
+            return dst.iter_mut().zip(src).for_each(|(d, s)| {
+                let mut s = RGBA::<f32>::from(*s);  s.a *= opacity;
+                *d = s.compose((*d).into(), self).demula().into()
+            })
+        }
+
+        dst.iter_mut().zip(src).for_each(|(d, s)| {
+            let s = RGBA { a: mul255(s.a as u32, opacity) as _, ..*s };
+            match self {   // fast paths
+                Self::SrcOver | Self::Lighter if s.a == 0 => (),
+                Self::SrcOver if s.a == 255 => *d = s,
+                _ => *d = s.composite(*d, self.factors(s.a as _, d.a as _)),
+            }
+        })
+    }
+
+    /// Make a span compositor of the mode with opacity.
+    pub fn compositor(self, opacity: u8) -> SpanFn {
+        Box::new(move |dst, src| self.composite_span(dst, src, opacity))
+    }
+}
+
+impl RGBA<u8> {
     /// Composite: ao x Co = αs x Fa x Cs + αb x Fb x Cb, ao = αs x Fa + αb x Fb;
-    pub fn composite(self, dest: Self, fa: u8, fb: u8) -> Self {
-        let (fa, fb) =  ((fa as u32 * self.a as u32 + 128) >> 8,
-                                   (fb as u32 * dest.a as u32 + 128) >> 8);
-        let a = (fa + fb).min(255);     // XXX: make it non-premultiplied?
-        let r = (fa * self.r as u32 + fb * dest.r as u32 + 128) >> 8; // / a;
-        let g = (fa * self.g as u32 + fb * dest.g as u32 + 128) >> 8; // / a;
-        let b = (fa * self.b as u32 + fb * dest.b as u32 + 128) >> 8; // / a;
-        Self { r: r as _, g: g as _, b: b as _, a: a as _ }
+    /// Output NON-premultiplied color (unlike `RGBA<f32>`) to be stored back into span/image.
+    fn composite(self, dest: Self, (fa, fb): (u32, u32)) -> Self {
+        let (fa, fb) = (mul255(fa, self.a as _), mul255(fb, dest.a as _));
+        let a = (fa + fb).min(255);     if a == 0 { return Self::zeroed() }
+        //  the pre-multiplied sums (of Lighter) clamped as well as the alpha
+        let op = |cs: u8, cb: u8| ((fa * cs as u32 + fb * cb as u32 + a / 2) / a).min(255) as u8;
+        Self { r: op(self.r, dest.r), g: op(self.g, dest.g), b: op(self.b, dest.b), a: a as _ }
     }
 
-    /// Apply the blend in place: Cs = (1 - αb) x Cs + αb x B(Cb, Cs)
+    /* /// Apply the blend in place: Cs = (1 - αb) x Cs + αb x B(Cb, Cs)
     pub fn blend(self, drop: Self, bop: impl Fn(u8, u8) -> u8) -> Self {
         //lerp(self.r, bop(drop.r, self.r), drop.a);    // lerp for g, b as well
         let (inv_a, da) = ((255 - drop.a) as u32, drop.a as u32);
@@ -123,8 +205,8 @@ pub use crate::color::RGBA;
         let g = (inv_a * self.g as u32 + da * bop(drop.g, self.g) as u32 + 128) >> 8;
         let b = (inv_a * self.b as u32 + da * bop(drop.b, self.b) as u32 + 128) >> 8;
         Self { r: r as _, g: g as _, b: b as _, a: self.a }
-    }
-} */
+    } */
+}
 
 /** ```
     use ugl_rs::blend::RGBA;
@@ -205,6 +287,69 @@ impl RGBA<f32> {    #![allow(unused)]
         Self { r, g, b,  a: inv_a * self.a + drop.a } // XXX: self.a
     }
 
+    /// Apply the non-separable blend result `bc` = B(Cb, Cs) in place, as `blend` does.
+    fn mix(self, drop: Self, bc: Self) -> Self {
+        let inv_a = 1. - drop.a;
+        Self {  r: inv_a * self.r + drop.a * bc.r, g: inv_a * self.g + drop.a * bc.g,
+                b: inv_a * self.b + drop.a * bc.b, a: inv_a * self.a + drop.a }
+    }
+
+    /** Blend (if a blending mode) and then composite source over destination/backdrop,
+        output pre-multiplied color with alpha, as the Porter-Duff operators do.
+    ```
+    use ugl_rs::blend::{BlendMode, RGBA};
+    let draw = RGBA::new(1.0, 0.0, 0.0, 1.0);
+    let back = RGBA::new(0.5, 0.5, 0.5, 1.0);
+
+    assert_eq!(draw.compose(back, BlendMode::SrcOver), draw);
+    assert_eq!(draw.compose(back, BlendMode::Dest),    back);
+    assert_eq!(draw.compose(back, BlendMode::Multiply), (0.5, 0.0, 0.0, 1.0).into());
+
+    let diff = |a: RGBA<f32>, b: RGBA<f32>|
+        (a.r - b.r).abs().max((a.g - b.g).abs()).max((a.b - b.b).abs());
+
+    //  a gray backdrop keeps gray with saturation modes, but takes lightness/value
+    assert_eq!(draw.compose(back, BlendMode::SaturationHSL), back);
+    assert_eq!(draw.compose(back, BlendMode::HueHSV), back);
+    assert!(diff(back.compose(draw, BlendMode::Lightness), (1.0, 0.0, 0.0).into()) < 1e-6);
+    assert!(diff(back.compose(draw, BlendMode::Value),     (0.5, 0.0, 0.0).into()) < 1e-6);
+
+    let draw = RGBA::new(0.2, 0.4, 0.6, 1.0);
+    let back = RGBA::new(0.9, 0.3, 0.1, 1.0);
+    let  out = draw.compose(back, BlendMode::Intensity);
+    assert!(((out.r + out.g + out.b) / 3. - 0.4).abs() < 1e-6 && diff(out, back) < 0.3);
+    let  out = draw.compose(back, BlendMode::Luminosity);   // luma of source
+    assert!((0.299 * out.r + 0.587 * out.g + 0.114 * out.b - 0.363).abs() < 1e-5);
+    ``` */
+    pub fn compose(self, dest: Self, mode: BlendMode) -> Self {
+        use BlendMode::*;
+        let blend = match mode {
+            Clear   => return self.clear(),     Copy    => return self.copy(dest),
+            Dest    => return self.drop(dest),  SrcOver => return self.src_over(dest),
+            SrcIn   => return self.src_in(dest),    SrcOut  => return self.src_out(dest),
+            SrcAtop => return self.src_atop(dest),  DstOver => return self.dst_over(dest),
+            DstIn   => return self.dst_in(dest),    DstOut  => return self.dst_out(dest),
+            DstAtop => return self.dst_atop(dest),  XOR     => return self.xor(dest),
+            Lighter => return self.lighter(dest),
+
+            Normal  => self.normal(dest),       Multiply   => self.multiply(dest),
+            Screen  => self.screen(dest),       Overlay    => self.overlay(dest),
+            Darken  => self.darken(dest),       Lighten    => self.lighten(dest),
+            ColorDodge => self.dodge(dest),     ColorBurn  => self.burn(dest),
+            HardLight  => self.hard_light(dest), SoftLight => self.soft_light(dest),
+            Difference => self.difference(dest), Exclusion => self.exclusion(dest),
+
+            Hue     => self.hue(dest),          Saturation => self.saturation(dest),
+            Color   => self.color(dest),        Luminosity => self.luminosity(dest),
+            HueHSL  => self.hue_hsl(dest),      SaturationHSL => self.saturation_hsl(dest),
+            ColorHSL => self.color_hsl(dest),   Lightness  => self.lightness(dest),
+            HueHSV  => self.hue_hsv(dest),      SaturationHSV => self.saturation_hsv(dest),
+            ColorHSV => self.color_hsv(dest),   Value      => self.value(dest),
+            HueHSI  => self.hue_hsi(dest),      SaturationHSI => self.saturation_hsi(dest),
+            ColorHSI => self.color_hsi(dest),   Intensity  => self.intensity(dest),
+        };  Self { a: self.a, ..blend }.src_over(dest)
+    }
+
     /// This is the default attribute which specifies no blending.
     /// The blending formula simply selects the source color.
     #[inline] pub fn normal(self, _: Self) -> Self { self } // XXX: self.blend(drop, |_, cs| cs)
@@ -252,13 +397,26 @@ impl RGBA<f32> {    #![allow(unused)]
             if cs == 0. { 0. } else { 1. - ((1. - cb) / cs).min(1.) })
     }
 
-    /// Overlay is the inverse of the hard-light blend mode.
-    #[inline] pub fn overlay(self, drop: Self) -> Self { drop.hard_light(self) }
+    /** Overlay is the inverse of the hard-light blend mode, i.e. `B(Cb, Cs)` of hard light
+        with the source and backdrop swapped, but composited onto the backdrop as others.
+    ```
+        use ugl_rs::blend::RGBA;
+        let draw = RGBA::new(1.0, 0.0, 0.0, 1.0);
+        let back = RGBA::new(0.0, 0.0, 1.0, 0.5);   // the source shows through by 1 - αb
+        assert_eq!(draw.overlay(back), (0.5, 0.0, 0.5, 1.0).into());
+        let lum = draw.luminosity(RGBA { a: 1.0, ..back });  // so do the non-separable modes
+        assert_eq!(draw.luminosity(back), (0.5 + lum.r / 2., lum.g / 2., lum.b / 2., 1.0).into());
+    ``` */
+    #[inline] pub fn overlay(self, drop: Self) -> Self {
+        self.blend(drop, |cb, cs| Self::hard_light_op(cs, cb))
+    }
     /// Multiplies or screens the colors, depending on the source color value.
     /// The effect is similar to shining a harsh spotlight on the backdrop.
     #[inline] pub fn hard_light(self, drop: Self) -> Self {
-        self.blend(drop, |cb, cs|
-            if cs <= 0.5 { cb * cs * 2. } else { 1. - (1. - cb) * (1. - cs) * 2. })
+        self.blend(drop, Self::hard_light_op)
+    }
+    #[inline] fn hard_light_op(cb: f32, cs: f32) -> f32 {
+        if cs <= 0.5 { cb * cs * 2. } else { 1. - (1. - cb) * (1. - cs) * 2. }
     }
 
     /// Darkens or lightens the colors, depending on the source color value.
@@ -285,24 +443,97 @@ impl RGBA<f32> {    #![allow(unused)]
     /// Creates a color with the hue of the source color and
     /// the saturation and luminosity of the backdrop color.
     #[inline] pub fn hue(self, drop: Self) -> Self {    // synonymous with chroma?
-        self.set_sat(drop.to_sat()).set_lum(drop.to_lum())
+        self.mix(drop, self.set_sat(drop.to_sat()).set_lum(drop.to_lum()))
     }
 
     /// Creates a color with the saturation of the source color and the hue and luminosity
     /// of the backdrop color. Painting with this mode in an area of the backdrop that is
     /// a pure gray (no saturation) produces no change.
     #[inline] pub fn saturation(self, drop: Self) -> Self {
-        let lum = drop.to_lum(); drop.set_sat(self.to_sat()).set_lum(lum)
+        let lum = drop.to_lum(); self.mix(drop, drop.set_sat(self.to_sat()).set_lum(lum))
     }
 
     /// Creates a color with the hue and saturation of the source color and the luminosity
     /// of the backdrop color. This preserves the gray levels of the backdrop and is useful
     /// for coloring monochrome images or tinting color images.
-    #[inline] pub fn color(self, drop: Self) -> Self { self.set_lum(drop.to_lum()) }
+    #[inline] pub fn color(self, drop: Self) -> Self {
+        self.mix(drop, self.set_lum(drop.to_lum()))
+    }
+
+    /** Creates a color with the luminosity of the source color and the hue and saturation
+        of the backdrop color. This produces an inverse effect to that of the Color mode.
+    ```
+        use ugl_rs::blend::RGBA;
+        let luma = |c: RGBA<f32>| 0.299 * c.r + 0.587 * c.g + 0.114 * c.b;
+        let draw = RGBA::new(0.2, 0.4, 0.6, 1.0);
+        let back = RGBA::new(0.9, 0.3, 0.1, 1.0);
+        let out  = draw.luminosity(back);  // shifted to the luma of source, not clipped only
+        assert!((luma(out) - luma(draw)).abs() < 1e-6 && out.r > out.g && out.g > out.b);
+        let out  = draw.saturation(back);  // chroma of source, minimum channel kept lowest
+        let chroma = out.r.max(out.g).max(out.b) - out.r.min(out.g).min(out.b);
+        assert!((chroma - 0.4).abs() < 1e-6 && (luma(out) - luma(back)).abs() < 1e-6);
+    ``` */
+    #[inline] pub fn luminosity(self, drop: Self) -> Self {
+        self.mix(drop, drop.set_lum(self.to_lum()))
+    }
+
+    /// Hue of the source with saturation and lightness of the backdrop in HSL model.
+    /// A hue taken from an achromatic color yields gray, like what Krita does.
+    #[inline] pub fn hue_hsl(self, drop: Self) -> Self {
+        let (h, s, _) = self.to_hsl();
+        self.mix(drop, if s == 0. { drop.set_sat_hsl(0.) } else { drop.set_hue_hsl(h) })
+    }
+    /// Saturation of the source with hue and lightness of the backdrop in HSL model.
+    #[inline] pub fn saturation_hsl(self, drop: Self) -> Self {
+        let s = if drop.to_hsl().1 == 0. { 0. } else { self.to_hsl().1 };
+        self.mix(drop, drop.set_sat_hsl(s))
+    }
+    /// Hue and saturation of the source with lightness of the backdrop in HSL model.
+    #[inline] pub fn color_hsl(self, drop: Self) -> Self {
+        self.mix(drop, self.set_lum_hsl(drop.to_hsl().2))
+    }
+    /// Lightness of the source with hue and saturation of the backdrop in HSL model.
+    #[inline] pub fn lightness(self, drop: Self) -> Self {
+        self.mix(drop, drop.set_lum_hsl(self.to_hsl().2))
+    }
 
-    /// Creates a color with the luminosity of the source color and the hue and saturation
-    /// of the backdrop color. This produces an inverse effect to that of the Color mode.
-    #[inline] pub fn luminosity(self, drop: Self) -> Self { drop.set_lum(self.to_lum()) }
+    /// Hue of the source with saturation and value of the backdrop in HSV model.
+    #[inline] pub fn hue_hsv(self, drop: Self) -> Self {
+        let (h, s, _) = self.to_hsv();
+        self.mix(drop, if s == 0. { drop.set_sat_hsv(0.) } else { drop.set_hue_hsv(h) })
+    }
+    /// Saturation of the source with hue and value of the backdrop in HSV model.
+    #[inline] pub fn saturation_hsv(self, drop: Self) -> Self {
+        let s = if drop.to_hsv().1 == 0. { 0. } else { self.to_hsv().1 };
+        self.mix(drop, drop.set_sat_hsv(s))
+    }
+    /// Hue and saturation of the source with value of the backdrop in HSV model.
+    #[inline] pub fn color_hsv(self, drop: Self) -> Self {
+        self.mix(drop, self.set_val_hsv(drop.to_hsv().2))
+    }
+    /// Value of the source with hue and saturation of the backdrop in HSV model.
+    #[inline] pub fn value(self, drop: Self) -> Self {
+        self.mix(drop, drop.set_val_hsv(self.to_hsv().2))
+    }
+
+    /// Hue of the source with saturation and intensity of the backdrop in HSI model.
+    #[inline] pub fn hue_hsi(self, drop: Self) -> Self {
+        let (h, s, _) = self.to_hsi();
+        self.mix(drop, if s == 0. { drop.set_sat_hsi(0.) } else { drop.set_hue_hsi(h) })
+    }
+    /// Saturation of the source with hue and intensity of the backdrop in HSI model.
+    #[inline] pub fn saturation_hsi(self, drop: Self) -> Self {
+        let s = if drop.to_hsi().1 == 0. { 0. } else { self.to_hsi().1 };
+        self.mix(drop, drop.set_sat_hsi(s))
+    }
+    /// Hue and saturation of the source with intensity of the backdrop in HSI model.
+    #[inline] pub fn color_hsi(self, drop: Self) -> Self {
+        self.mix(drop, self.set_int_hsi(drop.to_hsi().2))
+    }
+    /// Intensity of the source with hue and saturation of the backdrop in HSI model.
+    #[inline] pub fn intensity(self, drop: Self) -> Self {
+        self.mix(drop, drop.set_int_hsi(self.to_hsi().2))
+    }
 
     /// Luma is the weighted average of gamma-corrected R, G, and B, based on their contribution
     /// to perceived lightness, long used as the monochromatic dimension in color TV broadcast.
@@ -321,18 +552,19 @@ impl RGBA<f32> {    #![allow(unused)]
              if *cmid < *cmin {  cmid = cmin; cmin = &mut self.b }
         else if *cmax < *cmid {  cmid = cmax; cmax = &mut self.b }
 
-        if  *cmid <  *cmax {
+        if  *cmin <  *cmax {
             *cmid = (*cmid - *cmin) * sat / (*cmax - *cmin);    *cmax = sat
-        } else {     *cmid = 0.;    *cmax = 0.; }    *cmid = 0.;    self
+        } else {     *cmid = 0.;    *cmax = 0.; }    *cmin = 0.;    self
     }
 
     fn set_lum(mut self, lum: f32) -> Self {
-        let l = self.to_sat();  let d = lum - l;
-        let n = self.r.min(self.g).min(self.b) + d;
-        let x = self.r.max(self.g).max(self.b) + d;
+        let d = lum - self.to_lum();    let l = lum;
+        self.r += d;    self.g += d;    self.b += d;
+        let n = self.r.min(self.g).min(self.b);
+        let x = self.r.max(self.g).max(self.b);
 
         if n < 0. {
-            let op = |c| l + (((c - l) * c) / (l - n));
+            let op = |c| l + (((c - l) * l) / (l - n));
             self.r = op(self.r); self.g = op(self.g); self.b = op(self.b);
         }
         if 1. < x {
@@ -430,6 +662,36 @@ impl RGBA<f32> {    #![allow(unused)]
         Self::from_hsv(hue, s, v, self.a)
     }
 
+    /// Intensity is the average of R, G, and B, saturation is relative to the intensity,
+    /// and hue is the hexagonal one as in HSL/HSV.
+    fn to_hsi(self) -> (f32, f32, f32) {
+        let i = (self.r + self.g + self.b) / 3.;
+        let s = if i == 0. { 0. } else { 1. - self.r.min(self.g).min(self.b) / i };
+        (self.to_hsv().0, s, i)
+    }
+
+    fn from_hsi(h: f32, s: f32, i: f32, a: f32) -> Self {
+        let c = Self::from_hsv(h, 1., 1., a);   // the pure hue with max 1 and min 0
+        let m = i * (1. - s);   let k = (i - m) * 3. / (c.r + c.g + c.b);
+        let op = |v: f32| (m + v * k).clamp(0., 1.);
+        Self { r: op(c.r), g: op(c.g), b: op(c.b), a }
+    }
+
+    #[inline] fn set_int_hsi(self, int: f32) -> Self {
+        let (h, s, _) = self.to_hsi();
+        Self::from_hsi(h, s, int, self.a)
+    }
+
+    #[inline] fn set_sat_hsi(self, sat: f32) -> Self {
+        let (h, _, i) = self.to_hsi();
+        Self::from_hsi(h, sat, i, self.a)
+    }
+
+    #[inline] fn set_hue_hsi(self, hue: f32) -> Self {
+        let (_, s, i) = self.to_hsi();
+        Self::from_hsi(hue, s, i, self.a)
+    }
+
     /// Simply divides pixel values of one layer with the other, but it's useful for
     /// brightening photos if the colour is on grey or less.
     /// It is also useful for removing a colour tint from a photo.
@@ -483,3 +745,138 @@ impl RGBA<f32> {    #![allow(unused)]
     //  https://docs.krita.org/en/reference_manual/blending_modes.html
 }
 
+
+/// Offscreen layer buffer of NON-premultiplied colors in row-major order.
+#[derive(Clone, Debug, PartialEq)] pub struct Layer {
+    pub width: usize, pub height: usize, pub data: Vec<RGBA<f32>>,
+}
+
+impl Layer {
+    /// Create a fully transparent layer.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { width, height, data: vec![RGBA::zeroed(); width * height] }
+    }
+
+    /// Fill the whole layer with the color.
+    pub fn fill(&mut self, clr: RGBA<f32>) { self.data.fill(clr) }
+
+    /// Composite the source layer (of the same size) onto this one in place.
+    pub fn composite(&mut self, src: &Self, mode: BlendMode) {
+        debug_assert!(self.width == src.width && self.height == src.height);
+        self.data.iter_mut().zip(&src.data).for_each(|(cb, cs)|
+            *cb = cs.compose(*cb, mode).demula());
+    }
+}
+
+/** Transparency group attributes, refer to section 11.4 of PDF 32000-1:2008 and
+    https://www.w3.org/TR/compositing-1/#isolationblending.
+    An isolated group is composited with a fully transparent initial backdrop,
+    while a non-isolated one starts from the parent's backdrop, whose contribution
+    is removed (backdrop removal) before the group is composited back into its parent.
+    Each element of a knockout group is composited with the group's initial backdrop
+    rather than the elements painted before it, which get knocked out by its shape.
+```
+    use ugl_rs::blend::{BlendMode, Group, Layer, RGBA};
+    let (gray, red, blue) = (RGBA::new(0.5, 0.5, 0.5, 1.), RGBA::red(), RGBA::blue());
+    let mut elem = Layer::new(2, 1);    elem.fill(red);
+
+    let group = |isolated, knockout| Group { isolated, knockout, ..Group::default() };
+    let paint = |group: Group, elems: &[&Layer], mode| {
+        let mut back = Layer::new(2, 1);    back.fill(gray);
+        let mut layer = group.begin(&back);
+        elems.iter().for_each(|elem| layer.paint(elem, None, mode));
+        layer.end(&mut back);   back.data[0]
+    };
+
+    //  Elements blend with the parent's backdrop only in a non-isolated group
+    assert_eq!(paint(group(false, false), &[&elem], BlendMode::Multiply),
+        (0.5, 0., 0., 1.).into());
+    assert_eq!(paint(group(true,  false), &[&elem], BlendMode::Multiply), red);
+
+    //  Backdrop removal makes a non-isolated group of normal elements act as if isolated
+    elem.fill(RGBA { a: 0.5, ..red });
+    assert_eq!(paint(group(false, false), &[&elem], BlendMode::SrcOver),
+               paint(group(true,  false), &[&elem], BlendMode::SrcOver));
+
+    //  The latter element knocks out the former in a knockout group
+    let mut half = Layer::new(2, 1);    half.fill(RGBA { a: 0.5, ..blue });
+    assert_eq!(paint(group(true, true), &[&half, &elem], BlendMode::SrcOver),
+        (0.75, 0.25, 0.25, 1.).into());
+    assert_ne!(paint(group(true, false), &[&half, &elem], BlendMode::SrcOver),
+        (0.75, 0.25, 0.25, 1.).into());
+``` */
+#[derive(Clone, Copy, Debug, PartialEq)] pub struct Group {
+    pub isolated: bool, pub knockout: bool,
+    /// to composite the group result into its parent
+    pub mode: BlendMode, pub opacity: f32,
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        Self { isolated: false, knockout: false, mode: BlendMode::SrcOver, opacity: 1. }
+    }
+}
+
+impl Group {
+    /// Begin painting the group onto an offscreen layer upon the parent's backdrop.
+    pub fn begin(self, parent: &Layer) -> GroupLayer {
+        let backdrop = if self.isolated {
+            Layer::new(parent.width, parent.height) } else { parent.clone() };
+        GroupLayer { alpha: vec![0.; backdrop.data.len()], layer: backdrop.clone(),
+            backdrop, attr: self }
+    }
+}
+
+/// Offscreen layer of a transparency group in painting.
+#[derive(Clone, Debug)] pub struct GroupLayer {
+    attr: Group, layer: Layer,
+    /// the group alpha (αg) alone, excluding the initial backdrop
+    alpha: Vec<f32>,
+    /// the initial backdrop (C0, α0), fully transparent for isolated group
+    backdrop: Layer,
+}
+
+impl GroupLayer {
+    /// The result so far, including the initial backdrop for non-isolated group.
+    #[inline] pub fn layer(&self) -> &Layer { &self.layer }
+
+    /// Paint an element onto the group layer. `shape` is the coverage (fs) of the element,
+    /// with alpha of `src` being its opacity (qs) then; without it, any non-zero alpha
+    /// is taken as full coverage for knocking out.
+    pub fn paint(&mut self, src: &Layer, shape: Option<&[f32]>, mode: BlendMode) {
+        debug_assert!(self.layer.width == src.width && self.layer.height == src.height);
+        for (i, cs) in src.data.iter().enumerate() {
+            let fs = shape.map_or(if 0. < cs.a { 1. } else { 0. }, |shape| shape[i]);
+            if fs <= 0. { continue }    let ag = &mut self.alpha[i];
+
+            if self.attr.knockout {
+                let (cr, cp) = (cs.compose(self.backdrop.data[i], mode),
+                    self.layer.data[i].mula());
+                let op = |cr: f32, cp: f32| cp + (cr - cp) * fs;
+                self.layer.data[i] = RGBA { r: op(cr.r, cp.r), g: op(cr.g, cp.g),
+                    b: op(cr.b, cp.b), a: op(cr.a, cp.a) }.demula();
+                *ag += (cs.a - *ag) * fs;
+            } else {
+                let cs = if shape.is_some() { RGBA { a: cs.a * fs, ..*cs } } else { *cs };
+                self.layer.data[i] = cs.compose(self.layer.data[i], mode).demula();
+                *ag += cs.a - *ag * cs.a;
+            }
+        }
+    }
+
+    /// Finish the group and composite the result into the parent layer,
+    /// removing the contribution of the initial backdrop for non-isolated group.
+    pub fn end(self, parent: &mut Layer) {
+        let Self { attr, layer, alpha, backdrop } = self;
+        for (i, cb) in parent.data.iter_mut().enumerate() {
+            let (c, ag) = (layer.data[i], alpha[i]);
+            if ag <= 0. { continue }
+
+            let c = if attr.isolated { c } else {
+                let c0 = backdrop.data[i];  let k = c0.a / ag - c0.a;
+                let op = |c: f32, c0: f32| (c + (c - c0) * k).clamp(0., 1.);
+                RGBA { r: op(c.r, c0.r), g: op(c.g, c0.g), b: op(c.b, c0.b), a: ag }
+            };  *cb = RGBA { a: ag * attr.opacity, ..c }.compose(*cb, attr.mode).demula();
+        }
+    }
+}
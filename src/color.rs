@@ -1,4 +1,6 @@
 
+use crate::math::approx;
+
 pub type Color = RGBA<u8>;
 
 /** ```
@@ -164,6 +166,9 @@ impl RGBA<f32> {    #![allow(unused)]
     pub fn mula(&self) -> Self {
         Self { r: self.r * self.a, g: self.g * self.a, b: self.b * self.a, a: self.a }
     }
+    pub fn demula(&self) -> Self {   if self.a <= 0. { return Self::zeroed() }
+        Self { r: self.r / self.a, g: self.g / self.a, b: self.b / self.a, a: self.a }
+    }
 
     //  Gamma Correction: https://en.wikipedia.org/wiki/Gamma_correction
     #[inline] fn fast_gamma_expand(v: f32) -> f32 { v * v }
@@ -171,7 +176,8 @@ impl RGBA<f32> {    #![allow(unused)]
 
     #[inline] fn gamma_expand(v: f32) -> f32 { v.powf(2.2) }
     #[inline] fn gamma_encode(v: f32) -> f32 { v.powf(1. / 2.2) }
-    // TODO: http://www.machinedlearnings.com/2011/06/fast-approximate-logarithm-exponential.html
+    #[inline] fn approx_gamma_expand(v: f32) -> f32 { approx::powf(v, 2.2) }
+    #[inline] fn approx_gamma_encode(v: f32) -> f32 { approx::powf(v, 1. / 2.2) }
 
     #[inline] fn srgb_gamma_expand(v: f32) -> f32 {
         if v <= 0.04045 { v / 12.92 } else { ((v + 0.055) / 1.055).powf(2.4) }
@@ -190,3 +196,37 @@ impl RGBA<f32> {    #![allow(unused)]
     } }
 }
 
+
+/// Pixel formats convertible from/to `Color` (the working format of span compositors).
+pub trait Pixel: Copy {
+    fn to_color(self) -> Color;
+    fn from_color(clr: Color) -> Self;
+}
+
+impl Pixel for RGBA<u8>  {
+    #[inline] fn to_color(self) -> Color { self }
+    #[inline] fn from_color(clr: Color) -> Self { clr }
+}
+
+impl Pixel for RGBA<u16> {   // full range mapping: 0xFF <-> 0xFFFF
+    #[inline] fn to_color(self) -> Color {
+        let op = |v: u16| ((v as u32 * 255 + 32895) >> 16) as u8;
+        Color { r: op(self.r), g: op(self.g), b: op(self.b), a: op(self.a) }
+    }
+    #[inline] fn from_color(clr: Color) -> Self {
+        let op = |v: u8| v as u16 * 257;
+        Self { r: op(clr.r), g: op(clr.g), b: op(clr.b), a: op(clr.a) }
+    }
+}
+
+impl Pixel for RGBA<f32> {
+    #[inline] fn to_color(self) -> Color { self.into() }
+    #[inline] fn from_color(clr: Color) -> Self { clr.into() }
+}
+
+impl Pixel for u8 {     // opaque gray/luma
+    #[inline] fn to_color(self) -> Color { Color::new(self, self, self, u8::MAX) }
+    #[inline] fn from_color(clr: Color) -> Self {
+        ((clr.r as u32 * 77 + clr.g as u32 * 150 + clr.b as u32 * 29 + 128) >> 8) as _
+    }
+}
@@ -2,3 +2,91 @@
 //  Point/Line/(Bezier)Curve, Shapes (Triangle/Rectangle/Polygon/Ellipse/Circle/Arc)
 //  Fill(solid/linear/radial/conic/texture)/Stroke(width/cap/join/dash), walk/text along path
 
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use crate::math::Scalar;
+
+pub mod rect;       // rectangle/bounds and region of rectangles
+pub use rect::{IRect, Rect, Region};
+pub mod path;       // path of lines and Bézier curves, and its builder
+pub use path::{FillRule, Path, PathBuilder, PathCmd, Segment, Verb};
+pub mod flatten;    // adaptive flattening of curves into lines
+pub mod stroke;     // stroking with width, caps, joins and miter limit
+pub use stroke::{LineCap, LineJoin, Stroke};
+pub mod dash;       // dash patterns splitting paths before stroking
+pub use dash::Dash;
+pub mod shapes;     // paths of rectangles, ellipses, arcs, polygons and stars
+pub use shapes::{ArcKind, Shape};
+pub mod svg;        // SVG path data parser and serializer
+pub use svg::{SvgError, SvgErrorKind};
+pub mod boolean;    // boolean operations of paths: union, intersect, difference and xor
+pub use boolean::BoolOp;
+pub mod tessellate; // triangulation of paths into indexed meshes with anti-aliasing fringe
+pub use tessellate::Mesh;
+pub mod measure;    // lengths, positions and tangents along paths, and sub-paths
+pub use measure::{ContourMeasure, PathMeasure};
+pub mod hittest;    // hit testing of fill and stroke, distances and nearest points on paths
+pub mod offset;     // parallel curves, and insetting and outsetting of paths
+pub mod simplify;   // simplification of paths by reducing lines and merging curves
+pub mod fit;        // least-squares fitting of cubic Bézier curves to points, incrementally
+pub use fit::CurveFitter;
+pub mod spline;     // Catmull-Rom, B-spline and NURBS curves converted into Bézier curves
+pub use spline::CatmullRom;
+pub mod morph;      // interpolation of paths normalized to compatible structures
+pub use morph::PathMorph;
+
+/// Point/Vector in 2D space, generic over the number type.
+#[derive(Clone, Copy, Debug, Default, PartialEq)] pub struct Point<T: Scalar = f32> {
+    pub x: T, pub y: T,
+}
+
+pub type Vector<T = f32> = Point<T>;
+
+impl<T: Scalar> Point<T> {
+    #[inline] pub fn new(x: T, y: T) -> Self { Self { x, y } }
+    #[inline] pub fn zero() -> Self { Self { x: T::ZERO, y: T::ZERO } }
+
+    #[inline] pub fn dot  (self, other: Self) -> T { self.x * other.x + self.y * other.y }
+    /// z component of the 3D cross product, positive if `other` is counter-clockwise
+    /// (in y-up coordinates) from `self`.
+    #[inline] pub fn cross(self, other: Self) -> T { self.x * other.y - self.y * other.x }
+    #[inline] pub fn length_squared(self) -> T { self.dot(self) }
+    #[inline] pub fn length(self) -> T { self.dot(self).sqrt() }
+    #[inline] pub fn distance(self, other: Self) -> T { (other - self).length() }
+
+    /// Rotated by 90 degrees: (x, y) -> (-y, x)
+    #[inline] pub fn perp(self) -> Self { Self { x: -self.y, y: self.x } }
+    #[inline] pub fn lerp(self, other: Self, t: T) -> Self { self + (other - self) * t }
+    #[inline] pub fn mid (self, other: Self) -> Self { (self + other) * T::HALF }
+
+    /// Unit vector in the same direction, `None` for zero-length vector.
+    #[inline] pub fn normalize(self) -> Option<Self> {
+        let len = self.length();    (T::ZERO < len).then(|| self / len)
+    }
+}
+
+impl<T: Scalar> From<(T, T)> for Point<T> {
+    #[inline] fn from((x, y): (T, T)) -> Self { Self { x, y } }
+}
+
+impl<T: Scalar> Add for Point<T> {  type Output = Self;
+    #[inline] fn add(self, rhs: Self) -> Self { Self { x: self.x + rhs.x, y: self.y + rhs.y } }
+}
+impl<T: Scalar> Sub for Point<T> {  type Output = Self;
+    #[inline] fn sub(self, rhs: Self) -> Self { Self { x: self.x - rhs.x, y: self.y - rhs.y } }
+}
+impl<T: Scalar> Mul<T> for Point<T> {   type Output = Self;
+    #[inline] fn mul(self, rhs: T) -> Self { Self { x: self.x * rhs, y: self.y * rhs } }
+}
+impl<T: Scalar> Div<T> for Point<T> {   type Output = Self;
+    #[inline] fn div(self, rhs: T) -> Self { Self { x: self.x / rhs, y: self.y / rhs } }
+}
+impl<T: Scalar> Neg for Point<T> {  type Output = Self;
+    #[inline] fn neg(self) -> Self { Self { x: -self.x, y: -self.y } }
+}
+impl<T: Scalar> AddAssign for Point<T> {
+    #[inline] fn add_assign(&mut self, rhs: Self) { *self = *self + rhs }
+}
+impl<T: Scalar> SubAssign for Point<T> {
+    #[inline] fn sub_assign(&mut self, rhs: Self) { *self = *self - rhs }
+}
+
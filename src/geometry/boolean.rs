@@ -0,0 +1,307 @@
+
+//  Boolean operations of paths by planar arrangement of the flattened edges:
+//  https://en.wikipedia.org/wiki/Boolean_operations_on_polygons
+//  https://www.angusj.com/clipper2/Docs/Overview.htm
+//  1. flatten curves of both operands (open contours are closed implicitly as filling),
+//     remembering the source curves and parameters of the edges;
+//  2. split edges at all intersections (by exact predicates), merge coincident edges
+//     with their winding contributions of each operand;
+//  3. classify both sides of every edge by the winding numbers with the fill rules,
+//     keep the edges separating the inside and outside of the result, oriented with
+//     the inside on the left (counter-clockwise in y-up coordinates);
+//  4. link them into contours, reconstructing the curves from runs of flattened edges,
+//     and merging collinear lines.
+
+use std::collections::HashMap;
+use crate::{geometry::{FillRule, Path, PathBuilder, PathCmd, Point, Segment},
+    math::{Scalar, predicates::{orient2d, segment_intersection, SegmentIntersection}}};
+
+/// Boolean operations of filled regions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)] pub enum BoolOp {
+    Union, Intersect,
+    /** the first operand subtracted by the second */ Difference,
+    Xor,
+}
+
+impl BoolOp {
+    #[inline] fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Self::Union => a || b,  Self::Intersect => a && b,
+            Self::Difference => a && !b,    Self::Xor => a != b,
+        }
+    }
+}
+
+type P = Point<f64>;
+
+#[derive(Clone, Copy, Debug)] struct Edge {
+    a: P, b: P,
+    /// winding contributions to the operands
+    wind: [i32; 2],
+    /// index of the source curve, and the parameters at `a` and `b`
+    src: Option<(usize, f64, f64)>,
+}
+
+impl Edge {
+    fn reversed(&self) -> Self {
+        Self { a: self.b, b: self.a, wind: self.wind.map(|w| -w),
+            src: self.src.map(|(id, ta, tb)| (id, tb, ta)) }
+    }
+}
+
+//  exact key of point, with -0 normalized
+#[inline] fn key(p: P) -> (u64, u64) { ((p.x + 0.).to_bits(), (p.y + 0.).to_bits()) }
+
+impl<T: Scalar> Path<T> {
+    /** Boolean operation with another path, respecting the fill rules of both, curves are
+        flattened within the tolerance, and reconstructed in the result where they remain.
+        The result has non-overlapping contours oriented by the non-zero rule (outer ones
+        counter-clockwise in y-up coordinates, i.e. clockwise in y-down, and holes reversed).
+        The union with an empty path removes self-intersections and overlaps.
+    ```
+        use ugl_rs::geometry::*;
+        let area = |path: &Path| path.flatten(1e-4).segments()
+            .map(|seg| seg.start().cross(seg.end())).sum::<f32>() / 2.;
+        let (a, b) = (Path::rect(Rect::new(0., 0., 2., 2.)),
+        Path::rect(Rect::new(1., 1., 2., 2.)));
+        assert_eq!(area(&a.boolean(&b, BoolOp::Union, 0.1)), 7.);
+        assert_eq!(area(&a.boolean(&b, BoolOp::Intersect, 0.1)), 1.);
+        assert_eq!(area(&a.boolean(&b, BoolOp::Difference, 0.1)), 3.);
+        assert_eq!(area(&a.boolean(&b, BoolOp::Xor, 0.1)), 6.);
+
+        let c = Path::rect(Rect::new(2., 0., 2., 2.));     // coincident edge
+        let union = a.boolean(&c, BoolOp::Union, 0.1);
+        assert_eq!(union.points().len(), 4);    assert_eq!(area(&union), 8.);
+
+        let mut pb = PathBuilder::new();    // nested squares in the same direction
+        pb.move_to(Point::new(0., 0.)).line_to(Point::new(4., 0.)).line_to(Point::new(4., 4.))
+          .line_to(Point::new(0., 4.)).close().move_to(Point::new(1., 1.))
+          .line_to(Point::new(3., 1.)).line_to(Point::new(3., 3.)).line_to(Point::new(1., 3.))
+          .close();
+        let mut ring = pb.build();  let empty = Path::default();
+        assert_eq!(area(&ring.boolean(&empty, BoolOp::Union, 0.1)), 16.);
+        ring.fill_rule = FillRule::EvenOdd;
+        assert_eq!(area(&ring.boolean(&empty, BoolOp::Union, 0.1)), 12.);
+
+        let bowtie = Path::polyline(&[Point::new(0., 0.), Point::new(2., 2.),
+            Point::new(2., 0.), Point::new(0., 2.)], true);     // self-intersecting
+        let clean = bowtie.boolean(&empty, BoolOp::Union, 0.1);
+        assert_eq!(clean.verbs().iter().filter(|v| **v == Verb::Close).count(), 2);
+        assert_eq!(area(&clean), 2.);
+
+        let (c0, c1) = (Path::circle(Point::new(0., 0.), 1.),
+        Path::circle(Point::new(1., 0.), 1.));
+        let lens = c0.boolean(&c1, BoolOp::Intersect, 1e-3);
+        assert!(lens.verbs().iter().all(|v| matches!(v, Verb::Move | Verb::Conic | Verb::Close)));
+        let expected = 2. * 0.5f32.acos() - 0.75f32.sqrt();
+        assert!((area(&lens) - expected).abs() < 1e-3, "{}", area(&lens));
+
+        let p = |x, y| Point::new(x, y);    // contours touching at (4, 2) don't cross there
+        let a = Path::polyline(&[p(0., 6.), p(4., 4.), p(6., 4.), p(4., 2.), p(4., 6.),
+            p(2., 2.)], true);
+        let b = Path::polyline(&[p(2., 0.), p(4., 2.), p(0., 2.), p(6., 4.), p(0., 6.),
+            p(6., 0.)], true);
+        let union = a.boolean(&b, BoolOp::Union, 0.1);
+        assert_eq!(union.points()[union.points().len() - 3..], [p(4., 2.), p(2., 0.), p(6., 0.)]);
+    ``` */
+    pub fn boolean(&self, other: &Self, op: BoolOp, tolerance: T) -> Self {
+        let rules = [self.fill_rule, other.fill_rule];
+        self.arrange(other, tolerance, |w| {
+            let filled = |k: usize| match rules[k] {
+                FillRule::NonZero => w[k] != 0, FillRule::EvenOdd => w[k] % 2 != 0 };
+            op.apply(filled(0), filled(1))
+        })
+    }
+
+    /// The region of both paths by the predicate of their winding numbers, see `boolean`.
+    pub(crate) fn arrange(&self, other: &Self, tolerance: T, inside: impl Fn([i32; 2]) -> bool)
+        -> Self {
+        let (mut curves, mut edges) = (Vec::new(), Vec::new());
+        for (k, path) in [self, other].into_iter().enumerate() {
+            collect_edges(path, k, tolerance.to_f64(), &mut curves, &mut edges);
+        }
+
+        //  snapped to the grid of power of 2 relative to the extent, and split again
+        //  for the (few) crossings introduced by the snapping
+        let extent = edges.iter().fold(0f64, |m, e|
+            m.max(e.a.x.abs()).max(e.a.y.abs()).max(e.b.x.abs()).max(e.b.y.abs()));
+        let grid = 2f64.powi(extent.max(f64::MIN_POSITIVE).log2().ceil() as i32 - 40);
+        let snap = |p: P| Point::new((p.x / grid).round() * grid, (p.y / grid).round() * grid);
+        edges = edges.into_iter().filter_map(|e| { let (a, b) = (snap(e.a), snap(e.b));
+            (a != b).then_some(Edge { a, b, ..e }) }).collect();
+        for _ in 0..4 { if !split(&mut edges, snap) { break } }
+        let edges = merge(edges);
+
+        let kept = (0..edges.len()).filter_map(|i| {
+            let (e, left) = (&edges[i], winding(&edges, i));
+            let right = [left[0] - e.wind[0], left[1] - e.wind[1]];
+            match (inside(left), inside(right)) {
+                (true, false) => Some(*e), (false, true) => Some(e.reversed()), _ => None,
+            }
+        }).collect();
+
+        let mut pb = PathBuilder::new();
+        link(kept).iter().filter(|contour| 2 < contour.len())   // dropping degenerate ones
+            .for_each(|contour| emit(contour, &curves, &mut pb));   pb.build()
+    }
+}
+
+fn collect_edges<T: Scalar>(path: &Path<T>, k: usize, tol: f64,
+    curves: &mut Vec<Segment<f64>>, edges: &mut Vec<Edge>) {
+    let cv = |p: Point<T>| Point::new(p.x.to_f64(), p.y.to_f64());
+    let mut wind = [0; 2];  wind[k] = 1;
+    let mut line = |a: P, b: P, src| if a != b { edges.push(Edge { a, b, wind, src }) };
+
+    let (mut start, mut last) = (Point::zero(), Point::zero());
+    //  the sentinel closes the last contour
+    for cmd in path.iter().chain([PathCmd::MoveTo(Point::zero())]) {
+        let seg = match cmd {
+            PathCmd::MoveTo(p) => { line(last, start, None);  (start, last) = (cv(p), cv(p));
+                continue }
+            PathCmd::Close => { line(last, start, None);  last = start;    continue }
+            PathCmd::LineTo(p) => { line(last, cv(p), None);  last = cv(p);   continue }
+            PathCmd::QuadTo(c, p) => Segment::Quad([last, cv(c), cv(p)]),
+            PathCmd::CubicTo(c1, c2, p) => Segment::Cubic([last, cv(c1), cv(c2), cv(p)]),
+            PathCmd::ConicTo(c, p, w) => Segment::Conic([last, cv(c), cv(p)], w.to_f64()),
+        };
+
+        let (id, n) = (curves.len(), seg.flatten_count(tol));   curves.push(seg);
+        let mut a = last;
+        for i in 1..=n {
+            let (t0, t1) = ((i - 1) as f64 / n as f64, i as f64 / n as f64);
+            let b = if i == n { seg.end() } else { seg.eval(t1) };
+            line(a, b, Some((id, t0, t1)));     a = b;
+        }   last = seg.end();
+    }
+}
+
+/// Split edges at the intersections with each other, by sweeping along x-axis,
+/// the intersection points are snapped to the grid, return whether any edge is split.
+fn split(edges: &mut Vec<Edge>, snap: impl Fn(P) -> P) -> bool {
+    let bbox = |e: &Edge|
+        (e.a.x.min(e.b.x), e.a.x.max(e.b.x), e.a.y.min(e.b.y), e.a.y.max(e.b.y));
+    let mut order: Vec<_> = (0..edges.len()).collect();
+    order.sort_by(|&i, &j| bbox(&edges[i]).0.total_cmp(&bbox(&edges[j]).0));
+
+    //  in canonical order of endpoints and edges, so that the (rounded) intersections
+    //  of coincident edges with others are identical
+    let canon = |e: &Edge| if key(e.a) <= key(e.b) { (e.a, e.b) } else { (e.b, e.a) };
+    let param = |e: &Edge, p: P| { let d = e.b - e.a;   (p - e.a).dot(d) / d.dot(d) };
+    let (mut cuts, mut changed) = (vec![Vec::new(); edges.len()], false);
+    for (n, &i) in order.iter().enumerate() {
+        let (ei, bi) = (&edges[i], bbox(&edges[i]));
+        for &j in &order[n + 1..] {
+            let (ej, bj) = (&edges[j], bbox(&edges[j]));
+            if bi.1 < bj.0 { break }
+            if bj.3 < bi.2 || bi.3 < bj.2 { continue }
+
+            let (mut s0, mut s1) = (canon(ei), canon(ej));
+            if (key(s1.0), key(s1.1)) < (key(s0.0), key(s0.1)) { (s0, s1) = (s1, s0) }
+            let pts = match segment_intersection(s0.0, s0.1, s1.0, s1.1) {
+                SegmentIntersection::None => continue,
+                SegmentIntersection::Cross(p) | SegmentIntersection::Touch(p) => [Some(p), None],
+                SegmentIntersection::Overlap(p, q) => [Some(p), Some(q)],
+            };
+            for p in pts.into_iter().flatten().map(&snap) {
+                for (k, e) in [(i, ei), (j, ej)] {
+                    if p != e.a && p != e.b { cuts[k].push((param(e, p).clamp(0., 1.), p)) }
+                }
+            }
+        }
+    }
+
+    *edges = edges.iter().zip(cuts).flat_map(|(e, mut cuts)| {
+        changed |= !cuts.is_empty();
+        cuts.sort_by(|a, b| a.0.total_cmp(&b.0));   cuts.dedup_by_key(|c| key(c.1));
+        let lerp = |s: f64| e.src.map(|(id, ta, tb)| (id, ta + (tb - ta) * s));
+        let mut last = (0., e.a);
+        cuts.into_iter().chain([(1., e.b)]).filter_map(move |(s, p)| {
+            let (s0, a) = last;     last = (s, p);
+            (a != p).then(|| Edge { a, b: p, wind: e.wind,
+                src: lerp(s0).zip(lerp(s)).map(|((id, ta), (_, tb))| (id, ta, tb)) })
+        }).collect::<Vec<_>>()
+    }).collect();   changed
+}
+
+/// Merge coincident edges by summing up the winding contributions.
+fn merge(edges: Vec<Edge>) -> Vec<Edge> {
+    let (mut map, mut out) = (HashMap::<_, usize>::new(), Vec::<Edge>::new());
+    for e in edges {
+        let (ka, kb) = (key(e.a), key(e.b));
+        let k = if ka <= kb { (ka, kb) } else { (kb, ka) };
+        if let Some(&i) = map.get(&k) {
+            let m = &mut out[i];    let s = if key(m.a) == ka { 1 } else { -1 };
+            m.wind[0] += s * e.wind[0];     m.wind[1] += s * e.wind[1];
+        } else { map.insert(k, out.len());  out.push(e); }
+    }   out.retain(|e| e.wind != [0, 0]);   out
+}
+
+/// Winding numbers of the operands at the left side of the edge, by casting a ray
+/// from its middle point to the left, perpendicular to the edge.
+fn winding(edges: &[Edge], i: usize) -> [i32; 2] {
+    let e = &edges[i];  let (m, n) = (e.a.mid(e.b), (e.b - e.a).perp());
+    let mut w = [0; 2];
+    for (j, o) in edges.iter().enumerate() {
+        let (sa, sb) = (n.cross(o.a - m), n.cross(o.b - m));    // sides of the ray line
+        if j == i || (0. < sa) == (0. < sb) { continue }
+        if (o.a.lerp(o.b, sa / (sa - sb)) - m).dot(n) <= 0. { continue }
+        let s = if 0. < sb - sa { 1 } else { -1 };  // direction crossing the ray
+        w[0] += s * o.wind[0];  w[1] += s * o.wind[1];
+    }   w
+}
+
+/// Link the oriented edges into closed contours, turning left most at the vertices
+/// to separate the touching contours.
+fn link(edges: Vec<Edge>) -> Vec<Vec<Edge>> {
+    let mut outgoing = HashMap::<_, Vec<usize>>::new();
+    edges.iter().enumerate().for_each(|(i, e)| outgoing.entry(key(e.a)).or_default().push(i));
+    let turn = |d: P, e: &Edge| { let v = e.b - e.a;    d.cross(v).atan2(d.dot(v)) };
+
+    let (mut used, mut contours) = (vec![false; edges.len()], Vec::new());
+    for s in 0..edges.len() {
+        if used[s] { continue }
+        let (mut contour, mut i) = (Vec::new(), s);
+        loop {
+            used[i] = true;     contour.push(edges[i]);     let e = &edges[i];
+            //  closed only if the start edge is the left most turn, at vertices touched again
+            let next = outgoing.get(&key(e.b)).and_then(|out| out.iter()
+                .filter(|&&j| !used[j] || j == s).max_by(|&&j, &&k|
+                    turn(e.b - e.a, &edges[j]).total_cmp(&turn(e.b - e.a, &edges[k]))));
+            match next { Some(&j) if j != s => i = j, _ => break }
+        }   contours.push(contour);
+    }   contours
+}
+
+fn emit<T: Scalar>(contour: &[Edge], curves: &[Segment<f64>], pb: &mut PathBuilder<T>) {
+    let cv = |p: P| Point::new(T::from_f64(p.x), T::from_f64(p.y));
+    //  continuous parameters of the same curve, or collinear lines
+    let joined = |e0: &Edge, e1: &Edge| match (e0.src, e1.src) {
+        (Some((i, _, t0)), Some((j, t1, _))) => i == j && t0 == t1,
+        (None, None) => orient2d(e0.a, e0.b, e1.b) == 0. && 0. < (e0.b - e0.a).dot(e1.b - e1.a),
+        _ => false,
+    };
+
+    let n = contour.len();
+    let r = (0..n).find(|&i| !joined(&contour[(i + n - 1) % n], &contour[i])).unwrap_or(0);
+    let edges: Vec<_> = contour[r..].iter().chain(&contour[..r]).collect();
+
+    pb.move_to(cv(edges[0].a));
+    let mut i = 0;
+    while i < n {
+        let mut j = i;  while j + 1 < n && joined(edges[j], edges[j + 1]) { j += 1 }
+        let (e0, e1) = (edges[i], edges[j]);
+        let Some((id, t0, _)) = e0.src else {
+            if j + 1 < n { pb.line_to(cv(e1.b)); }  i = j + 1;  continue    // closed by `close`
+        };
+        let t1 = e1.src.unwrap().2;
+
+        //  snapped to the vertices of the arrangement
+        match curves[id].sub(t0, t1) {
+            Segment::Line(_) => { pb.line_to(cv(e1.b)); }
+            Segment::Quad([_, c, _]) => { pb.quad_to(cv(c), cv(e1.b)); }
+            Segment::Cubic([p0, c1, c2, p3]) => {
+                pb.cubic_to(cv(c1 + (e0.a - p0)), cv(c2 + (e1.b - p3)), cv(e1.b)); }
+            Segment::Conic([_, c, _], w) => { pb.conic_to(cv(c), cv(e1.b), T::from_f64(w)); }
+        }   i = j + 1;
+    }   pb.close();
+}
@@ -0,0 +1,152 @@
+
+//  https://www.w3.org/TR/SVG2/painting.html#StrokeDashing
+//  https://github.com/google/skia/blob/main/src/utils/SkDashPath.cpp
+//  Curves are flattened within the tolerance to measure the lengths along them,
+//  and the pattern restarts at the beginning of each sub-path.
+
+use crate::{geometry::{flatten::length, Path, PathBuilder, PathCmd, Point}, math::Scalar};
+
+/** Dash pattern of alternating lengths of on/off intervals, shifted by the phase offset.
+```
+    use ugl_rs::geometry::*;
+    let p = |x, y| Point::new(x, y);
+    let mut pb = PathBuilder::new();    pb.move_to(p(0., 0.)).line_to(p(10., 0.));
+    let line = pb.build();
+
+    let dashed = line.dash(&Dash::new(&[2., 1.], 0.).unwrap(), 0.1);
+    assert_eq!(dashed.points(), &[p(0., 0.), p(2., 0.), p(3., 0.), p(5., 0.),
+        p(6., 0.), p(8., 0.), p(9., 0.), p(10., 0.)]);
+    let dashed = line.dash(&Dash::new(&[2., 1.], -2.).unwrap(), 0.1);  // phase 1 of period 3
+    assert_eq!(dashed.points()[..2], [p(0., 0.), p(1., 0.)]);
+    assert_eq!(Dash::new(&[1.], 0.).unwrap().array(), &[1., 1.]);
+    assert!(Dash::new(&[0., 0.], 0.).is_none() && Dash::new(&[1., -1.], 0.).is_none());
+
+    let dotted = line.dash(&Dash::new(&[0., 2.], 0.).unwrap(), 0.1);    // zero-length dashes
+    assert_eq!(dotted.verbs().iter().filter(|v| **v == Verb::Move).count(), 6);
+    let style = Stroke { width: 1., cap: LineCap::Round, ..Default::default() };
+    let stroked = dotted.stroke(&style, 0.1);
+    assert_eq!(stroked.verbs().iter().filter(|v| **v == Verb::Close).count(), 6);
+
+    let mut pb = PathBuilder::new();    // the last dash is joined with the first one
+    pb.move_to(p(0., 0.)).line_to(p(10., 0.)).line_to(p(10., 10.)).line_to(p(0., 10.))
+      .close();
+    let dashed = pb.build().dash(&Dash::new(&[5., 5.], 2.).unwrap(), 0.1);
+    assert_eq!(dashed.verbs().iter().filter(|v| **v == Verb::Move).count(), 4);
+    assert!(dashed.points().contains(&p(0., 0.)));
+
+    let mut pb = PathBuilder::new();    let r = Vector::new(100., 100.);
+    pb.move_to(p(100., 0.)).arc_to(r, 0., false, true, p(-100., 0.))
+      .arc_to(r, 0., false, true, p(100., 0.)).close();
+    let half = Dash::new(&[core::f32::consts::PI * 100., 1e3], 0.).unwrap();
+    let dashed = pb.build().dash(&half, 0.01);
+    let end = dashed.points()[dashed.points().len() - 1];   // half of the circle
+    assert!((end - p(-100., 0.)).length() < 0.05, "{end:?}");
+``` */
+#[derive(Clone, Debug, PartialEq)] pub struct Dash<T: Scalar = f32> {
+    array: Vec<T>, offset: T,
+    ends: Vec<T>,   // accumulated lengths at the ends of intervals
+}
+
+impl<T: Scalar> Dash<T> {
+    /// `None` if any of the lengths is negative, or all of them are zero.
+    /// The array is repeated if it has odd number of lengths (the same as SVG).
+    pub fn new(array: &[T], offset: T) -> Option<Self> {
+        if array.iter().any(|&v| v < T::ZERO) { return None }
+        let mut array = array.to_vec();
+        if array.len() % 2 == 1 { array.extend_from_within(..) }
+
+        let mut sum = T::ZERO;
+        let ends: Vec<_> = array.iter().map(|&v| { sum += v;  sum }).collect();
+        (T::ZERO < sum).then_some(Self { array, offset, ends })
+    }
+
+    #[inline] pub fn array (&self) -> &[T] { &self.array }
+    #[inline] pub fn offset(&self) -> T { self.offset }
+    /// Total length of the intervals.
+    #[inline] pub fn period(&self) -> T { self.ends[self.ends.len() - 1] }
+
+    /** Interpolation to the other at `t`, of the lengths (both arrays repeated to the least
+        common multiple of their counts) and the offset, `None` as `new` by extrapolation.
+    ```
+        use ugl_rs::geometry::*;
+        let (a, b) = (Dash::new(&[2., 2.], 0.).unwrap(), Dash::new(&[4., 2., 0., 2.], 4.).unwrap());
+        let mid = a.lerp(&b, 0.5).unwrap();
+        assert_eq!((mid.array(), mid.offset()), (&[3., 2., 1., 2.][..], 2.));
+        assert!(a.lerp(&b, -2.).is_none());
+    ``` */
+    pub fn lerp(&self, other: &Self, t: T) -> Option<Self> {
+        let (m, n) = (self.array.len(), other.array.len());
+        let gcd = { let (mut a, mut b) = (m, n);    while b != 0 { (a, b) = (b, a % b) }   a };
+        let array: Vec<_> = (0..m / gcd * n)
+            .map(|i| self.array[i % m].lerp(other.array[i % n], t)).collect();
+        Self::new(&array, self.offset.lerp(other.offset, t))
+    }
+
+    /// Index of the interval and its remaining length at the start of sub-paths,
+    /// by binary searching for long arrays.
+    fn start(&self) -> (usize, T) {
+        let period = self.period();
+        let phase = self.offset - (self.offset / period).floor() * period;
+        //  zero-length intervals at the phase are kept, but not the positive one ending there
+        let mut i = self.ends.partition_point(|&e| e < phase);
+        if i < self.ends.len() && self.ends[i] == phase && T::ZERO < self.array[i] { i += 1 }
+        if i < self.ends.len() { (i, self.ends[i] - phase) } else { (0, self.array[0]) }
+    }
+
+    fn contour(&self, pts: &[Point<T>], closed: bool, pb: &mut PathBuilder<T>) {
+        if pts.is_empty() { return }
+        let (mut i, mut remain) = self.start();
+        let first_on = i % 2 == 0;
+        let mut dashes = if first_on { vec![vec![pts[0]]] } else { vec![] };
+
+        let mut a = pts[0];
+        for &b in pts[1..].iter().chain(closed.then_some(&pts[0])) {
+            let (len, mut pos) = (length(b - a), T::ZERO);
+            while remain <= len - pos {     // the interval ends within the segment
+                pos += remain;
+                let pt = if len <= pos { b } else { a.lerp(b, pos / len) };
+                if i % 2 == 0 { dashes.last_mut().unwrap().push(pt) } else { dashes.push(vec![pt]) }
+                i = (i + 1) % self.array.len();     remain = self.array[i];
+            }
+
+            remain -= len - pos;
+            if i % 2 == 0 { let dash = dashes.last_mut().unwrap();
+                if dash.last() != Some(&b) { dash.push(b) }
+            }   a = b;
+        }
+
+        if closed && first_on && i % 2 == 0 {
+            if dashes.len() == 1 {  // not dashed at all
+                let dash = &dashes[0];  pb.move_to(dash[0]);
+                dash[1..dash.len() - 1].iter().for_each(|&pt| { pb.line_to(pt); });
+                pb.close();     return
+            }
+            let first = dashes.remove(0);
+            dashes.last_mut().unwrap().extend_from_slice(&first[1..]);
+        }
+
+        for dash in dashes.iter().filter(|dash| 1 < dash.len()) {
+            pb.move_to(dash[0]);    dash[1..].iter().for_each(|&pt| { pb.line_to(pt); });
+        }
+    }
+}
+
+impl<T: Scalar> Path<T> {
+    /// Split into dashes (as open sub-paths of lines) by the pattern, to be stroked then,
+    /// with curves flattened within the tolerance. The closed sub-path is kept closed if
+    /// it's not interrupted by the pattern.
+    pub fn dash(&self, dash: &Dash<T>, tolerance: T) -> Self {
+        let mut pb = PathBuilder::new();    pb.fill_rule(self.fill_rule);
+        let mut pts = Vec::new();
+        for cmd in self.flatten(tolerance).iter() {
+            match cmd {
+                PathCmd::MoveTo(p) => {
+                    dash.contour(&pts, false, &mut pb);     pts.clear();    pts.push(p);
+                }
+                PathCmd::LineTo(p) => pts.push(p),
+                PathCmd::Close => { dash.contour(&pts, true, &mut pb);  pts.clear(); }
+                _ => unreachable!(),
+            }
+        }   dash.contour(&pts, false, &mut pb);     pb.build()
+    }
+}
@@ -0,0 +1,327 @@
+
+//  https://github.com/erich666/GraphicsGems/blob/master/gems/FitCurves.c
+//  Philip J. Schneider, An Algorithm for Automatically Fitting Digitized Curves, 1990.
+//  Points are parameterized by chord lengths, the lengths of the handles along the tangents
+//  at the ends are solved by least squares, improved by Newton's iterations of the parameters
+//  with the distances along the normals minimized (converging faster than the distances),
+//  and split at the point of the maximum error (with the tangent by its neighbors) until
+//  within the tolerance. It's in `f64` for the sums of squares.
+
+use crate::{geometry::{Path, PathBuilder, Point, Segment}, math::Scalar};
+
+type P = Point<f64>;
+
+/// Maximum Newton's iterations of the parameters before splitting.
+const MAX_ITERATIONS: usize = 8;
+
+/// Maximum pending points of `CurveFitter` fitted by a single cubic, for bounded cost.
+const MAX_PENDING: usize = 256;
+
+impl<T: Scalar> Path<T> {
+    /** Convert the runs of lines into smooth cubics within the tolerance, e.g. polylines
+        of flattened curves or of freehand drawing, keeping the corners where the direction
+        turns by more than the angle (in radians), the single lines between corners,
+        and the curves.
+    ```
+        use ugl_rs::geometry::*;
+        let p = |x, y| Point::new(x, y);
+        let polygon = Path::circle(p(0., 0.), 100.).flatten(0.1);
+        let smooth = polygon.smooth(0.1, 0.5);
+        assert!(smooth.verbs().iter().all(|&verb| verb != Verb::Line));
+        assert!(smooth.verbs().len() * 4 < polygon.verbs().len());
+        assert!(smooth.segments().all(|seg| (0..=10).all(|k|
+            (seg.eval(k as f32 / 10.).length() - 100.).abs() <= 0.2)));
+
+        let square = Path::rect(Rect::new(0., 0., 10., 10.));  // corners are kept
+        assert_eq!(square.smooth(0.1, 0.5).verbs(), square.verbs());
+    ``` */
+    pub fn smooth(&self, tolerance: T, corner_angle: T) -> Self {
+        let cos_corner = corner_angle.sin_cos().1;
+        let cv = |p: Point<T>| Point::new(p.x.to_f64(), p.y.to_f64());
+        let dir = |seg: &Segment<T>| match *seg {
+            Segment::Line([p0, p1]) => cv(p1 - p0).normalize(), _ => None };
+        let corner = |a: &Segment<T>, b: &Segment<T>|
+            dir(a).zip(dir(b)).is_none_or(|(ua, ub)| ua.dot(ub) < cos_corner.to_f64());
+
+        let mut pb = PathBuilder::new();    pb.fill_rule(self.fill_rule);
+        for (mut segs, closed) in self.contours() {
+            segs.retain(|seg| !matches!(seg, Segment::Line([p0, p1]) if p0 == p1));
+            let n = segs.len();     if n == 0 { continue }
+            //  started at a corner for closed contour, or it's a smooth loop
+            let start = if closed {
+                (0..n).find(|&i| corner(&segs[(i + n - 1) % n], &segs[i]))
+            } else { Some(0) };
+            segs.rotate_left(start.unwrap_or(0));
+
+            pb.move_to(segs[0].start());
+            let mut i = 0;
+            while i < n {
+                if dir(&segs[i]).is_none() { pb.push_segment(segs[i]);    i += 1;     continue }
+                let mut j = i + 1;  while j < n && !corner(&segs[j - 1], &segs[j]) { j += 1 }
+                if j == i + 1 {     // the closing line by `close`
+                    if !(closed && j == n) { pb.push_segment(segs[i]); }     i = j;  continue
+                }
+
+                let pts: Vec<_> = core::iter::once(segs[i].start()).chain(segs[i..j].iter()
+                    .map(|seg| seg.end())).map(cv).collect();
+                let m = pts.len();
+                let (t0, t1) = if start.is_some() {
+                    (dir(&segs[i]).unwrap(), -dir(&segs[j - 1]).unwrap())
+                } else {
+                    let t = (pts[1] - pts[m - 2]).normalize().unwrap_or(dir(&segs[0]).unwrap());
+                    (t, -t)
+                };
+
+                let mut cubics = Vec::new();
+                fit_cubics(&pts, t0, t1, tolerance.to_f64(), &mut cubics);
+                let cv = |p: P| Point::new(T::from_f64(p.x), T::from_f64(p.y));
+                cubics.into_iter().for_each(|[c1, c2, p]| { pb.cubic_to(cv(c1), cv(c2), cv(p)); });
+                i = j;
+            }   if closed { pb.close(); }
+        }   pb.build()
+    }
+}
+
+/** Incremental fitting of cubics to the points as they arrive, e.g. of pen and touch input.
+    The pending points are fitted by a single cubic, and when it's out of the tolerance
+    with a new point, the previous fitting is settled and the next one continues smoothly
+    from its end. Points within half the tolerance of the previous are skipped as noise,
+    and corners where the direction turns by more than the angle (in radians) are kept.
+    For all the points at once, `Path::smooth` of the polyline fits fewer cubics.
+```
+    use ugl_rs::geometry::*;
+    let p = |x, y| Point::new(x, y);
+    let mut fitter = CurveFitter::new(0.5, 1.);
+    for i in 0..=200 {  // noisy circle as it's drawn, with live fitting
+        let (s, c) = (i as f32 * core::f32::consts::TAU / 200.).sin_cos();
+        let r = 100. + if i % 2 == 0 { 0.2 } else { -0.2 };
+        fitter.push(p(r * c, r * s));
+        assert_eq!(fitter.path().segments().last().map_or(p(100.2, 0.), |seg| seg.end()),
+            p(r * c, r * s));
+    }
+    let path = fitter.finish();
+    assert!(path.verbs()[1..].iter().all(|&verb| verb == Verb::Cubic));
+    assert!(path.verbs().len() < 20);
+    assert!(path.segments().all(|seg| (0..=10).all(|k|
+        (seg.eval(k as f32 / 10.).length() - 100.).abs() < 0.5)));
+
+    (0..=40).for_each(|i| { fitter.push(p(i.min(20) as f32, (i - 20).max(0) as f32)); });
+    let corner = fitter.finish();   // of two straight lines
+    assert!(corner.points().contains(&p(20., 0.)));
+    assert!(corner.segments().all(|seg| (0..=10).all(|k| {
+        let pt = seg.eval(k as f32 / 10.);  pt.y.abs().min((pt.x - 20.).abs()) < 1e-3 })));
+
+    let mut fitter = CurveFitter::new(-1., 1.);     // coincident points even if no tolerance
+    fitter.push(p(1., 1.)).push(p(1., 1.)).push(p(2., 1.)).push(p(2., 1.));
+    assert_eq!(fitter.finish().points(), [p(1., 1.), p(4. / 3., 1.), p(5. / 3., 1.), p(2., 1.)]);
+``` */
+#[derive(Clone, Debug)] pub struct CurveFitter<T: Scalar = f32> {
+    settled: PathBuilder<T>,
+    pending: Vec<P>,            // points fitted by the pending cubic
+    fitted: Option<[P; 4]>,     // the pending cubic
+    tangent: Option<P>,         // at the start of pending points continuing the settled
+    skipped: Option<P>,         // the last point if it's skipped
+    tolerance: f64, cos_corner: f64,
+}
+
+impl<T: Scalar> CurveFitter<T> {
+    pub fn new(tolerance: T, corner_angle: T) -> Self {
+        Self { settled: PathBuilder::new(), pending: Vec::new(), fitted: None, tangent: None,
+            skipped: None, tolerance: tolerance.to_f64(),
+            cos_corner: corner_angle.sin_cos().1.to_f64() }
+    }
+
+    /// Append the point, and settle the fitted cubics that it doesn't fit in.
+    pub fn push(&mut self, pt: Point<T>) -> &mut Self {
+        let pt = Point::new(pt.x.to_f64(), pt.y.to_f64());
+        if self.pending.last().is_some_and(|&last|
+            last == pt || last.distance(pt) <= self.tolerance * 0.5) {
+            self.skipped = Some(pt);
+        } else { self.skipped = None;   self.add(pt); }     self
+    }
+
+    /// The settled cubics with the fitting of the pending points, for live drawing.
+    pub fn path(&self) -> Path<T> {
+        let mut pb = self.settled.clone();
+        if let Some(bez) = self.fitted { push_cubic(&mut pb, &bez) }     pb.build()
+    }
+
+    /// Take the whole path with the last point (even skipped), and reset the fitter.
+    pub fn finish(&mut self) -> Path<T> {
+        if let Some(pt) = self.skipped.take().filter(|pt| self.pending.last() != Some(pt)) {
+            self.add(pt)
+        }
+        self.settle();  let path = self.settled.build();
+        (self.pending, self.tangent) = (Vec::new(), None);  path
+    }
+
+    fn add(&mut self, pt: P) {
+        let m = self.pending.len();
+        if m == 0 {
+            let cv = |v: f64| T::from_f64(v);
+            self.settled.move_to(Point::new(cv(pt.x), cv(pt.y)));   self.pending.push(pt);  return
+        }
+        if 2 <= m {     // the last point is a corner
+            let (a, b) = (self.pending[m - 2], self.pending[m - 1]);
+            if (b - a).normalize().zip((pt - b).normalize())
+                .is_some_and(|(u, v)| u.dot(v) < self.cos_corner) {
+                self.settle();  self.tangent = None;
+            }
+        }
+
+        self.pending.push(pt);
+        match self.fit() {
+            Ok(bez) if self.pending.len() <= MAX_PENDING => self.fitted = Some(bez),
+            _ => {  // settle the previous fitting, and continue from its end smoothly
+                if let Some(prev) = self.fitted {
+                    self.tangent = (prev[3] - prev[2]).normalize().or(self.tangent);
+                }
+                self.pending.pop();     self.settle();  self.pending.push(pt);
+                self.fitted = self.fit().ok();
+            }
+        }
+    }
+
+    /// Fit the pending points by a single cubic, with the end tangent by the last points.
+    fn fit(&self) -> Result<[P; 4], usize> {
+        let (pts, m) = (&self.pending, self.pending.len());
+        let t0 = self.tangent.or((pts[2.min(m - 1)] - pts[0]).normalize())
+            .or((pts[1] - pts[0]).normalize());
+        let t1 = (pts[m.saturating_sub(3)] - pts[m - 1]).normalize()
+            .or((pts[m - 2] - pts[m - 1]).normalize());
+        //  no tangents of the points too close to be normalized, not fitted then
+        let (Some(t0), Some(t1)) = (t0, t1) else { return Err(m - 1) };
+        fit_single(pts, t0, t1, self.tolerance)
+    }
+
+    /// Append the pending cubic to the settled, and restart the pending from its end.
+    fn settle(&mut self) {
+        if let Some(bez) = self.fitted.take() { push_cubic(&mut self.settled, &bez) }
+        if let Some(&last) = self.pending.last() { self.pending = vec![last] }
+    }
+}
+
+fn push_cubic<T: Scalar>(pb: &mut PathBuilder<T>, bez: &[P; 4]) {
+    let cv = |p: P| Point::new(T::from_f64(p.x), T::from_f64(p.y));
+    pb.cubic_to(cv(bez[1]), cv(bez[2]), cv(bez[3]));
+}
+
+/// Cubics fitted to the points (at least 2) within the tolerance, with the unit tangents
+/// at both ends (`t1` pointing backward from the end), as control points after the start.
+pub(crate) fn fit_cubics(pts: &[P], t0: P, t1: P, tolerance: f64, out: &mut Vec<[P; 3]>) {
+    let split = match fit_single(pts, t0, t1, tolerance) {
+        Ok(bez) => { out.push([bez[1], bez[2], bez[3]]);   return }
+        Err(split) => split,
+    };
+    let tc = (pts[split - 1] - pts[split + 1]).normalize()
+        .or((pts[split - 1] - pts[split]).normalize()).unwrap_or(-t0);
+    fit_cubics(&pts[..=split], t0, tc, tolerance, out);
+    fit_cubics(&pts[split..], -tc, t1, tolerance, out);
+}
+
+/// Single cubic fitted to the points (at least 2) within the tolerance, with the unit
+/// tangents at both ends, or the index of the point of the maximum error.
+pub(crate) fn fit_single(pts: &[P], t0: P, t1: P, tolerance: f64) -> Result<[P; 4], usize> {
+    let (first, last) = (pts[0], pts[pts.len() - 1]);
+    if pts.len() == 2 {
+        let d = first.distance(last) / 3.;  return Ok([first, first + t0 * d, last + t1 * d, last])
+    }
+
+    let mut u = chord_params(pts);
+    let mut bez = fit_cubic(pts, &u, t0, t1);
+    let (mut best, mut err) = (bez, max_error(pts, &u, &bez));
+    for _ in 0..MAX_ITERATIONS {
+        if err.0 <= tolerance { break }
+        reparameterize(&bez, pts, &mut u);
+        let Some(next) = fit_normal(pts, &u, t0, t1, &bez) else { break };
+        bez = next;     let e = max_error(pts, &u, &bez);
+        if e.0 < err.0 { (best, err) = (bez, e) }
+    }   if err.0 <= tolerance { Ok(best) } else { Err(err.1) }
+}
+
+/// Cubic by least squares of the distances to the points at the parameters.
+fn fit_cubic(pts: &[P], u: &[f64], t0: P, t1: P) -> [P; 4] {
+    let (first, last) = (pts[0], pts[pts.len() - 1]);
+    let (mut c, mut x) = ([[0.; 2]; 2], [0.; 2]);
+    for (&p, &u) in pts.iter().zip(u) {
+        let [b0, b1, b2, b3] = bernstein(u);
+        let (a0, a1) = (t0 * b1, t1 * b2);
+        c[0][0] += a0.dot(a0);  c[0][1] += a0.dot(a1);  c[1][1] += a1.dot(a1);
+        let v = p - (first * (b0 + b1) + last * (b2 + b3));
+        x[0] += a0.dot(v);      x[1] += a1.dot(v);
+    }
+
+    let det = c[0][0] * c[1][1] - c[0][1] * c[0][1];
+    let len = first.distance(last);
+    let (mut alpha0, mut alpha1) = (0., 0.);
+    if f64::EPSILON < det.abs() {
+        alpha0 = (x[0] * c[1][1] - x[1] * c[0][1]) / det;
+        alpha1 = (c[0][0] * x[1] - c[0][1] * x[0]) / det;
+    }
+    //  fallback to the heuristic for non-positive or too short handles
+    if alpha0 < len * 1e-6 || alpha1 < len * 1e-6 { (alpha0, alpha1) = (len / 3., len / 3.) }
+    [first, first + t0 * alpha0, last + t1 * alpha1, last]
+}
+
+/// Cubic by least squares of the distances along the normals of the current cubic
+/// at the parameters (tangent distance minimization), `None` if it's degenerated.
+fn fit_normal(pts: &[P], u: &[f64], t0: P, t1: P, bez: &[P; 4]) -> Option<[P; 4]> {
+    let (first, last) = (bez[0], bez[3]);
+    let d1 = [bez[1] - bez[0], bez[2] - bez[1], bez[3] - bez[2]];
+    let (mut c, mut x) = ([[0.; 2]; 2], [0.; 2]);
+    for (&p, &u) in pts.iter().zip(u) {
+        let [b0, b1, b2, b3] = bernstein(u);   let mt = 1. - u;
+        let tan = d1[0] * (mt * mt) + d1[1] * (2. * mt * u) + d1[2] * (u * u);
+        let Some(n) = tan.perp().normalize() else { continue };
+        let (a0, a1) = (t0.dot(n) * b1, t1.dot(n) * b2);
+        let r = (p - (first * (b0 + b1) + last * (b2 + b3))).dot(n);
+        c[0][0] += a0 * a0;     c[0][1] += a0 * a1;     c[1][1] += a1 * a1;
+        x[0] += a0 * r;         x[1] += a1 * r;
+    }
+
+    let det = c[0][0] * c[1][1] - c[0][1] * c[0][1];
+    if det.abs() <= f64::EPSILON { return None }
+    let alpha0 = (x[0] * c[1][1] - x[1] * c[0][1]) / det;
+    let alpha1 = (c[0][0] * x[1] - c[0][1] * x[0]) / det;
+    (0. < alpha0 && 0. < alpha1).then(|| [first, first + t0 * alpha0, last + t1 * alpha1, last])
+}
+
+/// Maximum distance of the inner points to the cubic at the parameters, and the index.
+fn max_error(pts: &[P], u: &[f64], bez: &[P; 4]) -> (f64, usize) {
+    pts.iter().zip(u).enumerate().skip(1).take(pts.len() - 2)
+        .map(|(i, (&p, &u))| (eval(bez, u).distance(p), i))
+        .fold((0., pts.len() / 2), |a, b| if a.0 < b.0 { b } else { a })
+}
+
+/// Parameters in [0, 1] by the accumulated chord lengths.
+fn chord_params(pts: &[P]) -> Vec<f64> {
+    let mut sum = 0.;
+    let mut u: Vec<_> = pts.iter().enumerate().map(|(i, &p)| {
+        if 0 < i { sum += pts[i - 1].distance(p) }     sum }).collect();
+    let n = u.len();
+    if 0. < sum { u.iter_mut().for_each(|u| *u /= sum) }
+    else { u.iter_mut().enumerate().for_each(|(i, u)| *u = i as f64 / (n - 1) as f64) }
+    u
+}
+
+/// Newton's step of the parameters to the nearest points on the cubic.
+fn reparameterize(bez: &[P; 4], pts: &[P], u: &mut [f64]) {
+    let d1 = [bez[1] - bez[0], bez[2] - bez[1], bez[3] - bez[2]].map(|v| v * 3.);
+    let d2 = [d1[1] - d1[0], d1[2] - d1[1]].map(|v| v * 2.);
+    for (&p, u) in pts.iter().zip(u.iter_mut()) {
+        let [b0, b1, b2, b3] = bernstein(*u);   let mt = 1. - *u;
+        let q = bez[0] * b0 + bez[1] * b1 + bez[2] * b2 + bez[3] * b3;
+        let q1 = d1[0] * (mt * mt) + d1[1] * (2. * mt * *u) + d1[2] * (*u * *u);
+        let q2 = d2[0] * mt + d2[1] * *u;
+        let (num, den) = ((q - p).dot(q1), q1.dot(q1) + (q - p).dot(q2));
+        if f64::EPSILON < den.abs() { *u = (*u - num / den).clamp(0., 1.) }
+    }
+}
+
+fn bernstein(u: f64) -> [f64; 4] {
+    let mt = 1. - u;    [mt * mt * mt, 3. * mt * mt * u, 3. * mt * u * u, u * u * u]
+}
+
+fn eval(bez: &[P; 4], u: f64) -> P {
+    let [b0, b1, b2, b3] = bernstein(u);    bez[0] * b0 + bez[1] * b1 + bez[2] * b2 + bez[3] * b3
+}
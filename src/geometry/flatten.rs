@@ -0,0 +1,123 @@
+
+//  Flatten curves into line segments by uniform parametric steps with analytic counts:
+//  https://raphlinus.github.io/graphics/curves/2019/12/23/flatten-quadbez.html
+//  https://github.com/google/skia/blob/main/src/gpu/tessellate/WangsFormula.h
+//  Only the basic arithmetic and `sqrt` of `Scalar` are used, so it's in pure
+//  integer math for fixed-point types.
+
+use crate::{geometry::{Path, PathBuilder, PathCmd, Point, Segment, Vector}, math::Scalar};
+
+/// Upper limit of the number of line segments for a curve.
+pub const MAX_SEGMENTS: i32 = 1 << 10;
+
+//  scaled to avoid overflow of squares for fixed-point
+#[inline] pub(crate) fn length<T: Scalar>(v: Vector<T>) -> T {
+    let m = v.x.abs().max(v.y.abs());   if m <= T::ZERO { return T::ZERO }
+    let (x, y) = (v.x / m, v.y / m);    m * (x * x + y * y).sqrt()
+}
+
+impl<T: Scalar> Segment<T> {
+    /** Number of line segments (uniform in parameter) to approximate the curve,
+        so that the distance between them is within the tolerance, by Wang's formula
+        for quad/cubic, and by Sederberg's formula for conic. It's 1 for line (and conic
+        of non-positive weight), and clamped to `MAX_SEGMENTS`, with the tolerance at least
+        `EPSILON`.
+    ```
+        use ugl_rs::geometry::*;
+        let p = |x, y| Point::new(x, y);
+        let quad = Segment::Quad([p(0., 0.), p(50., 100.), p(100., 0.)]);
+        assert_eq!(quad.flatten_count(0.25), 15);   // ceil(sqrt(200 / 1))
+
+        let mut pts = vec![quad.start()];   quad.flatten(0.25, |pt| pts.push(pt));
+        assert_eq!(pts.len(), 16);  assert_eq!(pts.last(), Some(&quad.end()));
+        assert!(pts.windows(2).enumerate().all(|(i, w)| (1..10).all(|k| {
+            let t = (i as f32 + k as f32 / 10.) / 15.;  let pt = quad.eval(t);
+            let (d, v) = (w[1] - w[0], pt - w[0]);
+            (d.cross(v) / d.length()).abs() <= 0.25     // distance to the chord
+        })));
+
+        let conic = Segment::Conic([p(100., 0.), p(100., 100.), p(0., 100.)],
+            core::f32::consts::FRAC_1_SQRT_2);  // quarter circle of radius 100
+        let n = conic.flatten_count(0.1);   assert!(10 <= n && n <= 40, "{n}");
+        let mut pts = vec![conic.start()];  conic.flatten(0.1, |pt| pts.push(pt));
+        assert!(pts.windows(2).all(|w| 100. - w[0].mid(w[1]).length() <= 0.1));
+
+        #[cfg(feature = "fixed")] {     use ugl_rs::math::{I16F16, Scalar};
+            let f = |x: f32, y: f32| Point::new(I16F16::from_f32(x), I16F16::from_f32(y));
+            let cubic = Segment::Cubic([f(0., 0.), f(0., 300.), f(300., 300.), f(300., 0.)]);
+            assert_eq!(cubic.flatten_count(I16F16::from_f32(0.25)), 36);
+            let fc = Segment::Cubic([p(0., 0.), p(0., 300.), p(300., 300.), p(300., 0.)]);
+            let mut i = 0;  fc.flatten(0.25, |_| i += 1);   assert_eq!(i, 36);
+            assert_eq!(cubic.flatten_count(I16F16::ZERO), flatten::MAX_SEGMENTS);
+            let conic = Segment::Conic([f(0., 0.), f(1., 1.), f(2., 0.)], I16F16::ZERO);
+            assert_eq!(conic.flatten_count(I16F16::from_f32(0.25)), 1);
+        }
+    ``` */
+    pub fn flatten_count(&self, tolerance: T) -> i32 {
+        let tolerance = tolerance.max(T::EPSILON);
+        //  as the ratio `n2 = num / den`, to compare before overflow of fixed-point
+        let (num, den) = match self {
+            Self::Line(_) => return 1,
+            Self::Conic(_, w) if *w <= T::ZERO => return 1,     // degenerated into a line
+            Self::Quad(p) => (length(p[0] - p[1] * T::TWO + p[2]), tolerance * T::from_i32(4)),
+            Self::Cubic(p) => {
+                let m = length(p[0] - p[1] * T::TWO + p[2])
+                    .max(length(p[1] - p[2] * T::TWO + p[3]));
+                (m * T::from_i32(3), tolerance * T::from_i32(4))
+            }
+            Self::Conic(p, w) => {  let w = *w;
+                //  translated to the center of the bounds, for translation-invariance
+                let mid = |v: [T; 3]| (v[0].min(v[1]).min(v[2]) + v[0].max(v[1]).max(v[2])) * T::HALF;
+                let c = Point::new(mid(p.map(|p| p.x)), mid(p.map(|p| p.y)));
+                let (p0, p1, p2) = (p[0] - c, p[1] - c, p[2] - c);
+                let m = length(p0).max(length(p1)).max(length(p2));
+
+                let dp = p0 + p2 - p1 * (T::TWO * w);
+                let dw = (T::TWO - T::TWO * w).abs();
+                //  (|dp| / tol + (m / tol - 1) dw) / (4 min(w, 1)) multiplied by tol
+                (length(dp) + (m - tolerance).max(T::ZERO) * dw,
+                    tolerance * T::from_i32(4) * w.min(T::ONE))
+            }
+        };
+
+        let limit = T::from_i32(MAX_SEGMENTS * MAX_SEGMENTS);
+        if den <= T::ZERO || den <= num / limit { return MAX_SEGMENTS }
+        (num / den).sqrt().ceil().to_i32().clamp(1, MAX_SEGMENTS)
+    }
+
+    /// Flatten into line segments within the tolerance, calling back with the end points
+    /// of them (without the start point of the curve, and ending at its end point exactly).
+    pub fn flatten(&self, tolerance: T, mut f: impl FnMut(Point<T>)) {
+        let n = self.flatten_count(tolerance);  let step = T::ONE / T::from_i32(n);
+        (1..n).for_each(|i| f(self.eval(step * T::from_i32(i))));   f(self.end())
+    }
+}
+
+impl<T: Scalar> Path<T> {
+    /** Flatten curves into lines within the tolerance, contours are kept.
+    ```
+        use ugl_rs::geometry::*;
+        let mut pb = PathBuilder::new();
+        pb.move_to(Point::new(0., 0.)).line_to(Point::new(10., 0.))
+          .quad_to(Point::new(10., 10.), Point::new(0., 10.)).close();
+        let path = pb.build().flatten(0.1);
+        assert!(path.verbs().iter().all(|v| matches!(v, Verb::Move | Verb::Line | Verb::Close)));
+        assert_eq!(path.verbs().last(), Some(&Verb::Close));
+        assert_eq!(path.points().len(), 2 + Segment::Quad([Point::new(10., 0.),
+            Point::new(10., 10.), Point::new(0., 10.)]).flatten_count(0.1) as usize);
+    ``` */
+    pub fn flatten(&self, tolerance: T) -> Self {
+        let mut pb = PathBuilder::new();    pb.fill_rule(self.fill_rule);
+        let mut last = Point::zero();
+        for cmd in self.iter() {
+            let seg = match cmd {
+                PathCmd::MoveTo(p) => { last = p;   pb.move_to(p);   continue }
+                PathCmd::LineTo(p) => { last = p;   pb.line_to(p);   continue }
+                PathCmd::Close => { pb.close();  last = pb.current_point();    continue }
+                PathCmd::QuadTo(c, p) => Segment::Quad([last, c, p]),
+                PathCmd::CubicTo(c1, c2, p) => Segment::Cubic([last, c1, c2, p]),
+                PathCmd::ConicTo(c, p, w) => Segment::Conic([last, c, p], w),
+            };  seg.flatten(tolerance, |pt| { pb.line_to(pt); });  last = seg.end();
+        }   pb.build()
+    }
+}
@@ -0,0 +1,206 @@
+
+//  https://www.w3.org/TR/SVG2/painting.html#WindingRule
+//  https://pomax.github.io/bezierinfo/#projections
+//  Winding numbers are exact on curves: they are split into y-monotone pieces at extrema,
+//  and the crossings with the horizontal ray are found by bisection to the precision.
+//  The points are in the path's coordinates, for a path drawn with a transformation,
+//  map the point by the inverse transformation for fill and stroke hit testing,
+//  and transform the path for the metric queries of distances and nearest points.
+
+use crate::{geometry::{flatten::length, FillRule, LineCap, LineJoin, Path, Point, Rect,
+    Segment, Stroke}, math::Scalar};
+
+impl<T: Scalar> Segment<T> {
+    fn control_points(&self) -> &[Point<T>] {
+        match self { Self::Line(p) => p, Self::Quad(p) | Self::Conic(p, _) => p,
+            Self::Cubic(p) => p }
+    }
+
+    /// Winding contribution of the ray from the point to the right (+x).
+    fn winding(&self, pt: Point<T>) -> i32 {
+        let pts = self.control_points();
+        let (y0, y1) = pts.iter().fold((T::MAX, T::MIN), |(lo, hi), p| (lo.min(p.y), hi.max(p.y)));
+        if pt.y < y0 || y1 < pt.y || pts.iter().all(|p| p.x <= pt.x) { return 0 }
+        let left = pts.iter().all(|p| pt.x < p.x);  // crossing surely on the right
+
+        let mut ts = vec![T::ZERO, T::ONE];     self.extrema(&mut |t| ts.push(t));
+        ts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let mut winding = 0;
+        for w in ts.windows(2) {    // y-monotone pieces, half-open in y
+            let (mut a, mut b) = (w[0], w[1]);
+            let (pa, pb) = (self.eval(a), self.eval(b));
+            let dir = if pa.y < pb.y { 1 } else if pb.y < pa.y { -1 } else { continue };
+            if !(pa.y.min(pb.y) <= pt.y && pt.y < pa.y.max(pb.y)) { continue }
+            if left { winding += dir;   continue }
+
+            let mut x = pa.x;   // bisect for the crossing, with y(a) <= pt.y < y(b) in direction
+            for _ in 0..64 {
+                let m = (a + b) * T::HALF;  if m <= a || b <= m { break }
+                let pm = self.eval(m);  x = pm.x;
+                if (pm.y <= pt.y) == (0 < dir) { a = m } else { b = m }
+            }   if pt.x < x { winding += dir }
+        }   winding
+    }
+
+    /// Parameter of the nearest point on the segment and its distance to the point,
+    /// by projection for line, and by bisecting the sign change of `(B(t) - pt) · B'(t)`
+    /// around the nearest sample for curves.
+    pub(crate) fn nearest(&self, pt: Point<T>) -> (T, T) {
+        let dist = |t: T| length(self.eval(t) - pt);
+        if let Self::Line([a, b]) = *self {
+            let d = b - a;  let len2 = d.dot(d);
+            let t = if T::ZERO < len2 { ((pt - a).dot(d) / len2).clamp(T::ZERO, T::ONE) }
+                else { T::ZERO };   return (t, dist(t))
+        }
+
+        //  samples dense enough (by flattening) for the curvature relative to the size
+        let pts = self.control_points();
+        let size = pts.iter().map(|&p| length(p - pts[0])).fold(T::ZERO, T::max);
+        let n = self.flatten_count((size / T::from_i32(1 << 12)).max(T::EPSILON)).max(16);
+        let step = T::ONE / T::from_i32(n);
+        let ds: Vec<_> = (0..=n).map(|k| dist(step * T::from_i32(k))).collect();
+        let sign = |t: T| T::ZERO <= (self.eval(t) - pt)
+            .dot(self.tangent(t).normalize().unwrap_or(Point::zero()));
+
+        let mut candidates = Vec::new();    // around all local minima of samples
+        for k in (0..=n as usize).filter(|&k| (k == 0 || ds[k] <= ds[k - 1]) &&
+            (k == n as usize || ds[k] <= ds[k + 1])) {
+            let tk = step * T::from_i32(k as i32);  candidates.push(tk);
+            for (mut a, mut b) in [(tk - step, tk), (tk, tk + step)] {
+                (a, b) = (a.max(T::ZERO), b.min(T::ONE));
+                if b <= a || sign(a) || !sign(b) { continue }
+                for _ in 0..64 {
+                    let m = (a + b) * T::HALF;  if m <= a || b <= m { break }
+                    if sign(m) { b = m } else { a = m }
+                }   candidates.push(a);
+            }
+        }
+        candidates.into_iter().map(|t| (t, dist(t)))
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap()).unwrap()
+    }
+}
+
+impl<T: Scalar> Path<T> {
+    /// Winding number of the point, exact on curves, open contours are closed implicitly.
+    pub fn winding(&self, pt: Point<T>) -> i32 {
+        self.contours().iter().map(|(segs, closed)| {
+            let (start, end) = (segs[0].start(), segs[segs.len() - 1].end());
+            let close = if !closed && end != start {
+                Segment::Line([end, start]).winding(pt) } else { 0 };
+            segs.iter().map(|seg| seg.winding(pt)).sum::<i32>() + close
+        }).sum()
+    }
+
+    /** Whether the point is inside the fill of the path by the rule.
+    ```
+        use ugl_rs::{geometry::*, math::Transform2D};
+        let p = |x, y| Point::new(x, y);
+        let circle = Path::circle(p(0., 0.), 10.);
+        assert!(circle.contains(p(7., 7.), FillRule::NonZero));     // exact on curves
+        assert!(!circle.contains(p(7.1, 7.1), FillRule::NonZero));
+        assert!(!circle.contains(p(10., 0.), FillRule::NonZero));   // half-open
+
+        let inner = Path::circle(p(0., 0.), 5.);    // two circles in the same direction
+        let rings: Path = circle.iter().chain(inner.iter()).collect();
+        assert_eq!(rings.winding(p(0., 0.)).abs(), 2);
+        assert!(rings.contains(p(0., 0.), FillRule::NonZero));
+        assert!(!rings.contains(p(0., 0.), FillRule::EvenOdd));
+        assert!(rings.contains(p(7., 0.), FillRule::EvenOdd));
+
+        let mut pb = PathBuilder::new();    // open contour is closed implicitly
+        pb.move_to(p(0., 0.)).line_to(p(10., 0.)).line_to(p(10., 10.));
+        assert!(pb.build().contains(p(8., 2.), FillRule::NonZero));
+
+        let ts = Transform2D::translation(100., 0.).pre_scale(2., 1.);  // drawn transformed
+        let inv = ts.invert().unwrap();
+        assert!(circle.contains(inv.map_point(p(115., 0.)), FillRule::NonZero));
+        assert!(!circle.contains(inv.map_point(p(85., 8.)), FillRule::NonZero));
+    ``` */
+    pub fn contains(&self, pt: Point<T>, rule: FillRule) -> bool {
+        let winding = self.winding(pt);
+        match rule { FillRule::NonZero => winding != 0, FillRule::EvenOdd => winding % 2 != 0 }
+    }
+
+    /** Whether the point is inside the stroke of the path by the style, by the distance
+        for round caps and joins, and by the stroke outline (curves flattened within
+        the tolerance) else. It's within the tolerance of distance for zero width.
+    ```
+        use ugl_rs::geometry::*;
+        let p = |x, y| Point::new(x, y);
+        let mut pb = PathBuilder::new();    pb.move_to(p(0., 0.)).line_to(p(10., 0.));
+        let line = pb.build();
+        let mut style = Stroke { width: 2., ..Default::default() };
+        assert!(line.stroke_contains(p(5., 0.9), &style, 0.1));
+        assert!(!line.stroke_contains(p(5., 1.1), &style, 0.1));
+        assert!(!line.stroke_contains(p(-0.5, 0.5), &style, 0.1));  // butt cap
+        style.cap = LineCap::Square;
+        assert!(line.stroke_contains(p(-0.5, 0.5), &style, 0.1));
+        style.cap = LineCap::Round;     style.join = LineJoin::Round;
+        assert!(!line.stroke_contains(p(-0.8, 0.8), &style, 0.1));
+
+        let circle = Path::circle(p(0., 0.), 10.);
+        assert!(circle.stroke_contains(p(0., 10.9), &style, 0.1));
+        assert!(!circle.stroke_contains(p(0., 0.), &style, 0.1));   // not the fill
+        style.width = 0.;   assert!(circle.stroke_contains(p(6., 8.05), &style, 0.1));
+    ``` */
+    pub fn stroke_contains(&self, pt: Point<T>, style: &Stroke<T>, tolerance: T) -> bool {
+        let hw = style.width.abs() * T::HALF;
+        if hw <= T::ZERO { return self.distance(pt).is_some_and(|d| d <= tolerance) }
+
+        //  outside of the bounds outset by the extent of miter joins and square caps
+        let r = hw * style.miter_limit.max(T::from_f32(core::f32::consts::SQRT_2)) + tolerance;
+        let Some(rc) = self.bounds() else { return false };
+        if pt.x < rc.left - r || rc.right + r < pt.x || pt.y < rc.top - r || rc.bottom + r < pt.y {
+            return false
+        }
+
+        if style.cap == LineCap::Round && style.join == LineJoin::Round {
+            self.distance(pt).is_some_and(|d| d <= hw)
+        } else { self.stroke(style, tolerance).contains(pt, FillRule::NonZero) }
+    }
+
+    /** The nearest point on the path (its segments, with closing lines of closed contours)
+        to the point, and the distance, `None` if there is no segment.
+    ```
+        use ugl_rs::{geometry::*, math::Transform2D};
+        let p = |x, y| Point::new(x, y);
+        let circle = Path::circle(p(0., 0.), 10.);
+        let (pt, dist) = circle.nearest(p(20., 0.)).unwrap();
+        assert!((pt - p(10., 0.)).length() < 1e-4 && (dist - 10f32).abs() < 1e-4);
+        let (pt, dist) = circle.nearest(p(3., 4.)).unwrap();
+        assert!((pt - p(6., 8.)).length() < 1e-4 && (dist - 5f32).abs() < 1e-4);
+
+        let mut pb = PathBuilder::new();
+        pb.move_to(p(0., 0.)).cubic_to(p(0., 10.), p(10., 10.), p(10., 0.));
+        let cubic = pb.build();
+        assert!((cubic.distance(p(5., 10.)).unwrap() - 2.5f32).abs() < 1e-4);
+        assert_eq!(cubic.nearest(p(-1., -1.)).unwrap().0, p(0., 0.));
+        assert_eq!(Path::<f32>::default().distance(p(0., 0.)), None);
+
+        let ts = Transform2D::scaling(2., 1.);  // metric in the transformed coordinates
+        assert!((circle.transform(&ts).distance(p(30., 0.)).unwrap() - 10.).abs() < 1e-4);
+
+        #[cfg(feature = "fixed")] {     use ugl_rs::math::{I16F16, Scalar};
+            let f = |x: f32, y: f32| Point::new(I16F16::from_f32(x), I16F16::from_f32(y));
+            let mut pb = PathBuilder::new();    // smaller than the sampling tolerance
+            pb.move_to(f(0., 0.)).cubic_to(f(0., 0.01), f(0.01, 0.01), f(0.01, 0.));
+            assert!(pb.build().distance(f(1., 0.)).unwrap().to_f32() > 0.98);
+        }
+    ``` */
+    pub fn nearest(&self, pt: Point<T>) -> Option<(Point<T>, T)> {
+        let mut best: Option<(Point<T>, T)> = None;
+        for seg in self.segments() {
+            let rc = Rect::from_points(seg.control_points()).unwrap();
+            let out = Point::new((rc.left - pt.x).max(pt.x - rc.right).max(T::ZERO),
+                (rc.top - pt.y).max(pt.y - rc.bottom).max(T::ZERO));
+            if best.is_some_and(|(_, d)| d <= length(out)) { continue }     // culled by bounds
+
+            let (t, d) = seg.nearest(pt);
+            if best.is_none_or(|(_, bd)| d < bd) { best = Some((seg.eval(t), d)) }
+        }   best
+    }
+
+    /// Distance from the point to the path, see `nearest`.
+    #[inline] pub fn distance(&self, pt: Point<T>) -> Option<T> { self.nearest(pt).map(|n| n.1) }
+}
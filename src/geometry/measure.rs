@@ -0,0 +1,211 @@
+
+//  https://github.com/google/skia/blob/main/include/core/SkContourMeasure.h
+//  https://www.w3.org/TR/SVG2/text.html#TextLayoutPath
+//  Lengths are accumulated along the curves flattened within the tolerance, the parameters
+//  of the curves are interpolated between the flattened points, so the positions, tangents
+//  and sub-paths are exactly on the curves.
+
+use crate::{geometry::{flatten::length, Path, PathBuilder, Point, Segment, Vector},
+    math::{Scalar, Transform2D}};
+
+impl<T: Scalar> Segment<T> {
+    /// Derivative direction at the parameter t in [0, 1] (not normalized), falling back
+    /// to the chords of control points at the degenerated ends.
+    pub fn tangent(&self, t: T) -> Vector<T> {
+        let mt = T::ONE - t;
+        let d = match self {
+            Self::Line(p) => p[1] - p[0],
+            Self::Quad(p) => (p[1] - p[0]) * mt + (p[2] - p[1]) * t,
+            Self::Cubic(p) => (p[1] - p[0]) * (mt * mt) + (p[2] - p[1]) * (T::TWO * mt * t) +
+                (p[3] - p[2]) * (t * t),
+            Self::Conic(p, w) => {  // N'(t) W(t) - N(t) W'(t), translated by p0
+                let (w, q1, q2) = (*w, p[1] - p[0], p[2] - p[0]);
+                let (a, b, c) = (mt * mt, T::TWO * w * mt * t, t * t);
+                let (n, dn) = (q1 * b + q2 * c, q1 * (w * (mt - t)) + q2 * t);
+                dn * (a + b + c) - n * ((T::ONE - w) * (t - mt))
+            }
+        };
+        if d != Point::zero() { return d }
+        match self {
+            Self::Cubic(p) if t < T::HALF => if p[2] != p[0] { p[2] - p[0] } else { p[3] - p[0] },
+            Self::Cubic(p) => if p[3] != p[1] { p[3] - p[1] } else { p[3] - p[0] },
+            _ => self.end() - self.start(),
+        }
+    }
+}
+
+/** Measure of a contour, see `PathMeasure`.
+```
+    use ugl_rs::geometry::*;
+    let path = Path::rect(Rect::new(0., 0., 4., 3.));
+    let measure = PathMeasure::new(&path, 0.01);    let contour = &measure.contours()[0];
+    assert!(contour.is_closed() && contour.length() == 14.);
+
+    let mut pb = PathBuilder::new();    // across the corner (4, 0)
+    contour.sub_path(2., 6., &mut pb);  let sub = pb.build();
+    assert_eq!(sub.verbs(), [Verb::Move, Verb::Line, Verb::Line]);
+    assert_eq!(sub.points(), [Point::new(2., 0.), Point::new(4., 0.), Point::new(4., 2.)]);
+
+    let mut pb = PathBuilder::new();    contour.sub_path(-1., 20., &mut pb);
+    assert_eq!(pb.build().verbs().last(), Some(&Verb::Close));      // the whole contour
+``` */
+#[derive(Clone, Debug, PartialEq)] pub struct ContourMeasure<T: Scalar = f32> {
+    segs: Vec<Segment<T>>,
+    /// accumulated lengths at the ends of flattened lines, the segment index and parameter
+    samples: Vec<(T, usize, T)>,
+    closed: bool,
+}
+
+impl<T: Scalar> ContourMeasure<T> {
+    fn new(segs: Vec<Segment<T>>, closed: bool, tolerance: T) -> Self {
+        let (mut samples, mut sum) = (Vec::new(), T::ZERO);
+        for (i, seg) in segs.iter().enumerate() {
+            let n = seg.flatten_count(tolerance);   let step = T::ONE / T::from_i32(n);
+            let mut last = seg.start();
+            for k in 1..=n {
+                let t = if k == n { T::ONE } else { step * T::from_i32(k) };
+                let pt = seg.eval(t);   sum += length(pt - last);
+                samples.push((sum, i, t));  last = pt;
+            }
+        }   Self { segs, samples, closed }
+    }
+
+    #[inline] pub fn length(&self) -> T { self.samples.last().map_or(T::ZERO, |s| s.0) }
+    #[inline] pub fn is_closed(&self) -> bool { self.closed }
+
+    /// Segment index and its parameter at the distance (clamped to the contour).
+    fn locate(&self, distance: T) -> (usize, T) {
+        let d = distance.clamp(T::ZERO, self.length());
+        let k = self.samples.partition_point(|s| s.0 < d).min(self.samples.len() - 1);
+        let (d1, i, t1) = self.samples[k];
+        let (d0, t0) = match k.checked_sub(1).map(|k| self.samples[k]) {
+            Some((d0, j, t0)) if j == i => (d0, t0),
+            Some((d0, ..)) => (d0, T::ZERO),    None => (T::ZERO, T::ZERO),
+        };
+        (i, if d0 < d1 { t0 + (t1 - t0) * ((d - d0) / (d1 - d0)) } else { t1 })
+    }
+
+    /// Position and unit tangent at the distance (clamped to the contour),
+    /// the tangent is zero if the contour is degenerated to a point.
+    pub fn pos_tan(&self, distance: T) -> (Point<T>, Vector<T>) {
+        let (i, t) = self.locate(distance);     let seg = &self.segs[i];
+        (seg.eval(t), seg.tangent(t).normalize().unwrap_or(Point::zero()))
+    }
+
+    /// Append the part between the distances (clamped to the contour) as a new sub-path
+    /// to the builder, nothing if `d1 <= d0`. It's closed if the closed contour is whole.
+    pub fn sub_path(&self, d0: T, d1: T, pb: &mut PathBuilder<T>) {
+        let len = self.length();
+        let (d0, d1) = (d0.max(T::ZERO), d1.min(len));  if d1 <= d0 { return }
+        let ((i0, t0), (i1, t1)) = (self.locate(d0), self.locate(d1));
+
+        let first = self.segs[i0].sub(t0, if i0 == i1 { t1 } else { T::ONE });
+        pb.move_to(first.start());
+        pb.push_segment(first);
+        if i0 < i1 {
+            self.segs[i0 + 1..i1].iter().for_each(|&seg| { pb.push_segment(seg); });
+            pb.push_segment(self.segs[i1].sub(T::ZERO, t1));
+        }
+        if self.closed && d0 <= T::ZERO && len <= d1 { pb.close(); }
+    }
+}
+
+/** Measure of the lengths along a path, for positions and tangents at distances,
+    sub-paths between distances (e.g. animated line drawing), and placing items
+    (glyphs, markers) along the path. Distances of the path are along its contours
+    one after another, and the contours of zero length are skipped.
+```
+    use ugl_rs::{geometry::*, math::Transform2D};
+    let p = |x, y| Point::new(x, y);
+    let mut pb = PathBuilder::new();
+    pb.move_to(p(0., 0.)).line_to(p(3., 4.)).move_to(p(9., 9.)).move_to(p(10., 0.))
+      .arc_to(Vector::new(10., 10.), 0., false, true, p(-10., 0.));     // half circle
+    let measure = PathMeasure::new(&pb.build(), 0.01);
+    assert_eq!(measure.contours().len(), 2);
+    assert!((measure.length() - 5. - core::f32::consts::PI * 10.).abs() < 0.01);
+
+    assert_eq!(measure.pos_tan(2.5), Some((p(1.5, 2.), p(0.6, 0.8))));
+    let (pos, tan) = measure.pos_tan(5. + core::f32::consts::PI * 5.).unwrap();
+    assert!((pos - p(0., 10.)).length() < 1e-2 && (tan - p(-1., 0.)).length() < 1e-3);
+    assert_eq!(measure.pos_tan(1e3).unwrap().0, p(-10., 0.));   // clamped
+
+    let sub = measure.sub_path(1., 6.);     // for animated line drawing
+    assert_eq!(sub.verbs(), [Verb::Move, Verb::Line, Verb::Move, Verb::Conic]);
+    assert_eq!(sub.points()[..2], [p(0.6, 0.8), p(3., 4.)]);
+    assert!(sub.segments().all(|seg| (seg.eval(0.5).length() - 10f32).abs() < 1e-4 ||
+        matches!(seg, Segment::Line(_))));
+
+    let ts = measure.transform_at(2.5).unwrap();    // x-axis of the item along the tangent
+    assert_eq!(ts.map_point(p(0., 0.)), p(1.5, 2.));
+    assert_eq!(ts.map_vector(p(1., 0.)), p(0.6, 0.8));
+    let markers: Vec<_> = measure.place(2., 1.).collect();
+    assert_eq!(markers.len(), 18);
+    assert_eq!(markers[0].map_point(p(0., 0.)), p(0.6, 0.8));
+    assert!(PathMeasure::new(&Path::<f32>::default(), 0.1).transform_at(0.).is_none());
+
+    let mut pb = PathBuilder::new();    pb.move_to(p(0., 0.)).line_to(p(1e8, 0.));
+    let measure = PathMeasure::new(&pb.build(), 0.1);  // spacing below the ulp of distances
+    assert_eq!(measure.place(1., 1e8 - 100.).count(), 101);
+``` */
+#[derive(Clone, Debug, Default, PartialEq)] pub struct PathMeasure<T: Scalar = f32> {
+    contours: Vec<ContourMeasure<T>>,
+    ends: Vec<T>,   // accumulated lengths at the ends of contours
+}
+
+impl<T: Scalar> PathMeasure<T> {
+    /// Measure the path with curves flattened within the tolerance.
+    pub fn new(path: &Path<T>, tolerance: T) -> Self {
+        let mut contours: Vec<_> = path.contours().into_iter()
+            .map(|(segs, closed)| ContourMeasure::new(segs, closed, tolerance)).collect();
+
+        contours.retain(|contour| T::ZERO < contour.length());
+        let mut sum = T::ZERO;
+        let ends = contours.iter().map(|contour| { sum += contour.length();  sum }).collect();
+        Self { contours, ends }
+    }
+
+    #[inline] pub fn contours(&self) -> &[ContourMeasure<T>] { &self.contours }
+    /// Total length of all contours.
+    #[inline] pub fn length(&self) -> T { self.ends.last().copied().unwrap_or(T::ZERO) }
+
+    /// The contour index and the distance on it, clamped to the path.
+    fn locate(&self, distance: T) -> Option<(usize, T)> {
+        if self.contours.is_empty() { return None }
+        let i = self.ends.partition_point(|&e| e < distance).min(self.ends.len() - 1);
+        Some((i, distance - if 0 < i { self.ends[i - 1] } else { T::ZERO }))
+    }
+
+    /// Position and unit tangent at the distance (clamped to the path), `None` for empty path.
+    pub fn pos_tan(&self, distance: T) -> Option<(Point<T>, Vector<T>)> {
+        self.locate(distance).map(|(i, d)| self.contours[i].pos_tan(d))
+    }
+
+    /// Transformation to place an item at the distance, with its origin on the path,
+    /// and its x-axis along the tangent (its y-axis as the normal).
+    pub fn transform_at(&self, distance: T) -> Option<Transform2D<T>> {
+        self.pos_tan(distance).map(|(pos, tan)| Transform2D::new(tan.x, tan.y, -tan.y, tan.x,
+            pos.x, pos.y))
+    }
+
+    /// Transformations of items placed from the offset distance, at every spacing
+    /// (positive) distance until the end of the path.
+    pub fn place(&self, spacing: T, offset: T) -> impl Iterator<Item = Transform2D<T>> + '_ {
+        //  by the count of items, not accumulated as the spacing can be below the ulp
+        let (len, mut k) = (self.length(), 0);
+        core::iter::from_fn(move || {
+            let d = offset + spacing * T::from_i32(k);
+            if spacing <= T::ZERO || len < d { return None }
+            k += 1;     self.transform_at(d)
+        })
+    }
+
+    /// Part of the path between the distances, in sub-paths of the contours it spans.
+    pub fn sub_path(&self, d0: T, d1: T) -> Path<T> {
+        let mut pb = PathBuilder::new();
+        for (i, contour) in self.contours.iter().enumerate() {
+            let start = if 0 < i { self.ends[i - 1] } else { T::ZERO };
+            if d1 <= start { break }
+            contour.sub_path(d0 - start, d1 - start, &mut pb);
+        }   pb.build()
+    }
+}
@@ -0,0 +1,141 @@
+
+//  https://github.com/veltman/flubber
+//  https://www.w3.org/TR/SVG11/animate.html#complexDistances
+//  Both paths are normalized to pairs of contours with the same number of segments:
+//  the missing contours are collapsed to the centers of their counterparts, the longest
+//  segments are split in halves, and the closed contours are rotated to the start points
+//  of the least squared distances. Pairs of the same kind of segments are interpolated
+//  by their control points (and the weights of conics), others as cubics.
+
+use crate::{geometry::{FillRule, Path, PathBuilder, Point, Segment}, math::Scalar};
+
+/** Interpolation between two paths normalized to compatible structures, e.g. for tweening
+    of icons in animation, the intermediate path at `t` in [0, 1] is by `at`.
+```
+    use ugl_rs::geometry::*;
+    let p = |x, y| Point::new(x, y);
+    let square = Path::rect(Rect::new(-50., -50., 100., 100.));
+    let circle = Path::circle(p(0., 0.), 50.);
+    let morph = PathMorph::new(&square, &circle);
+    let (start, end) = (morph.at(0.), morph.at(1.));
+    assert_eq!(start.verbs(), end.verbs());
+    assert!(start.segments().all(|seg| (0..=10).all(|k| {
+        let pt = seg.eval(k as f32 / 10.);  (pt.x.abs().max(pt.y.abs()) - 50.).abs() < 1e-4 })));
+    assert!(end.segments().all(|seg| (0..=10).all(|k|
+        (seg.eval(k as f32 / 10.).length() - 50.).abs() < 0.05)));
+
+    let rotated = Path::polyline(&[p(50., 50.), p(-50., 50.), p(-50., -50.), p(50., -50.)], true);
+    let same = PathMorph::new(&square, &rotated).at(0.5);   // start points aligned
+    assert_eq!(same.verbs(), square.verbs());
+    assert!(same.points().iter().all(|pt| square.points().contains(pt)));
+
+    let triangle = Path::polyline(&[p(0., -50.), p(50., 50.), p(-50., 50.)], true);
+    let rings: Path = circle.iter().chain(Path::circle(p(0., 0.), 20.).iter()).collect();
+    let morph = PathMorph::new(&triangle, &rings);  // the inner circle grows from the center
+    assert_eq!(morph.at(0.).verbs().iter().filter(|&&verb| verb == Verb::Move).count(), 2);
+    assert!((0..=10).all(|k| morph.at(k as f32 / 10.).contains(p(0., 30.), FillRule::NonZero)));
+    assert!(!morph.at(1.).contains(p(0., 0.), FillRule::EvenOdd));
+    assert_eq!(square.lerp(&circle, 0.5), PathMorph::new(&square, &circle).at(0.5));
+
+    #[cfg(feature = "fixed")] {     use ugl_rs::math::{I16F16, Scalar};
+        let rect = |x| Path::rect(Rect::new(I16F16::from_i32(x), I16F16::ZERO,
+            I16F16::from_i32(200), I16F16::from_i32(200)));
+        let half = rect(0).lerp(&rect(100), I16F16::HALF);   // no overflow of squared distances
+        assert_eq!(half.bounds(), rect(50).bounds());
+    }
+``` */
+#[derive(Clone, Debug, PartialEq)] pub struct PathMorph<T: Scalar = f32> {
+    contours: Vec<MorphContour<T>>,
+    fill_rules: [FillRule; 2],
+}
+
+/// Pair of contours with the same number of segments, and whether they're closed.
+#[derive(Clone, Debug, PartialEq)] struct MorphContour<T: Scalar> {
+    segs: Vec<[Segment<T>; 2]>, closed: [bool; 2],
+}
+
+impl<T: Scalar> PathMorph<T> {
+    pub fn new(from: &Path<T>, to: &Path<T>) -> Self {
+        let (mut ca, mut cb) = (from.contours(), to.contours());
+        let n = ca.len().max(cb.len());
+        for i in 0..n {     // the missing contours collapsed to the centers of counterparts
+            if ca.len() <= i { ca.push(collapsed(&cb[i])) }
+            if cb.len() <= i { cb.push(collapsed(&ca[i])) }
+        }
+
+        let contours = ca.into_iter().zip(cb).map(|((mut sa, a), (mut sb, b))| {
+            while sa.len() < sb.len() { split_longest(&mut sa) }
+            while sb.len() < sa.len() { split_longest(&mut sb) }
+            if a && b {
+                let m = sa.len();
+                //  in f64 to avoid overflow of fixed-point, as it's only compared
+                let cost = |r: usize| (0..m).fold(0f64, |sum, i| {
+                    let d = sb[(i + r) % m].start() - sa[i].start();
+                    let (x, y) = (d.x.to_f64(), d.y.to_f64());  sum + x * x + y * y
+                });
+                let r = (1..m).fold((0, cost(0)), |best, r| {
+                    let c = cost(r);    if c < best.1 { (r, c) } else { best }
+                }).0;   sb.rotate_left(r);
+            }
+            MorphContour { segs: sa.into_iter().zip(sb).map(|(sa, sb)| [sa, sb]).collect(),
+                closed: [a, b] }
+        }).collect();
+        Self { contours, fill_rules: [from.fill_rule, to.fill_rule] }
+    }
+
+    /// The intermediate path at `t` in [0, 1], with the fill rule and whether the contours
+    /// are closed from the nearer end.
+    pub fn at(&self, t: T) -> Path<T> {
+        let end = usize::from(T::HALF <= t);
+        let mut pb = PathBuilder::new();    pb.fill_rule(self.fill_rules[end]);
+        for contour in &self.contours {
+            let (segs, closed) = (&contour.segs, contour.closed[end]);
+            pb.move_to(segs[0][0].start().lerp(segs[0][1].start(), t));
+            //  the closing line by `close`
+            let n = segs.len() - usize::from(closed &&
+                matches!(segs[segs.len() - 1], [Segment::Line(_), Segment::Line(_)]));
+            segs[..n].iter().for_each(|&[a, b]| { pb.push_segment(lerp(a, b, t)); });
+            if closed { pb.close(); }
+        }   pb.build()
+    }
+}
+
+impl<T: Scalar> Path<T> {
+    /// The intermediate path to the other at `t` in [0, 1], see `PathMorph`
+    /// for interpolating a pair of paths repeatedly.
+    pub fn lerp(&self, other: &Self, t: T) -> Self { PathMorph::new(self, other).at(t) }
+}
+
+/// Contour of the segments collapsed to the center of the start points.
+fn collapsed<T: Scalar>((segs, closed): &(Vec<Segment<T>>, bool)) -> (Vec<Segment<T>>, bool) {
+    let sum = segs.iter().fold(Point::zero(), |sum, seg| sum + seg.start());
+    let c = sum / T::from_i32(segs.len() as i32);
+    (segs.iter().map(|seg| match *seg {
+        Segment::Line(_) => Segment::Line([c; 2]),  Segment::Quad(_) => Segment::Quad([c; 3]),
+        Segment::Cubic(_) => Segment::Cubic([c; 4]),
+        Segment::Conic(_, w) => Segment::Conic([c; 3], w),
+    }).collect(), *closed)
+}
+
+/// Split the segment of the longest control polygon in halves.
+fn split_longest<T: Scalar>(segs: &mut Vec<Segment<T>>) {
+    let len = |seg: &Segment<T>| {
+        let [p0, p1, p2, p3] = seg.to_cubic();
+        p0.distance(p1) + p1.distance(p2) + p2.distance(p3)
+    };
+    let i = (1..segs.len()).fold(0, |i, j| if len(&segs[i]) < len(&segs[j]) { j } else { i });
+    let (a, b) = segs[i].split(T::HALF);   segs[i] = a;     segs.insert(i + 1, b);
+}
+
+/// Segment interpolated by the control points of the same kind, or as cubics.
+fn lerp<T: Scalar>(a: Segment<T>, b: Segment<T>, t: T) -> Segment<T> {
+    fn mix<T: Scalar, const N: usize>(pa: [Point<T>; N], pb: [Point<T>; N], t: T)
+        -> [Point<T>; N] { core::array::from_fn(|i| pa[i].lerp(pb[i], t)) }
+    match (a, b) {
+        (Segment::Line(pa), Segment::Line(pb)) => Segment::Line(mix(pa, pb, t)),
+        (Segment::Quad(pa), Segment::Quad(pb)) => Segment::Quad(mix(pa, pb, t)),
+        (Segment::Conic(pa, wa), Segment::Conic(pb, wb)) =>
+            Segment::Conic(mix(pa, pb, t), wa.lerp(wb, t)),
+        _ => Segment::Cubic(mix(a.to_cubic(), b.to_cubic(), t)),
+    }
+}
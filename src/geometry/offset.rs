@@ -0,0 +1,216 @@
+
+//  https://pomax.github.io/bezierinfo/#offsetting
+//  https://raphlinus.github.io/curves/2022/09/09/parallel-beziers.html
+//  Parallel curves are approximated by cubics with the end points and tangents exact, and
+//  the handles scaled by the curvature as `1 + d κ`, subdivided until within the tolerance.
+//  Paths are offset by their cleaned contours (see `boolean`) with joins at the convex
+//  corners and lines through the vertices at the concave ones, as the region plus (outset)
+//  or minus (inset) the regions swept by the normals, resolved by positive winding like
+//  Clipper. Inverted parts of curves (radius of curvature less than the distance) are
+//  flattened, since the swept regions of their parallel curves cancel out each other.
+
+use crate::{geometry::{BoolOp, LineJoin, Path, PathBuilder, Point, Segment, Vector},
+    math::Scalar};
+
+/// Maximum depth of subdividing a curve for its parallel curve.
+const MAX_DEPTH: u32 = 8;
+
+impl<T: Scalar> Segment<T> {
+    /// Control points of the cubic of the same curve, approximated for conic.
+    pub(crate) fn to_cubic(self) -> [Point<T>; 4] {
+        let third = T::ONE / T::from_i32(3);
+        match self {
+            Self::Line([p0, p1]) => [p0, p0.lerp(p1, third), p1.lerp(p0, third), p1],
+            Self::Quad([p0, c, p2]) =>
+                [p0, p0.lerp(c, third * T::TWO), p2.lerp(c, third * T::TWO), p2],
+            Self::Cubic(p) => p,
+            Self::Conic([p0, c, p2], w) => {
+                let k = w * T::from_i32(4) * third / (T::ONE + w);
+                [p0, p0.lerp(c, k), p2.lerp(c, k), p2]
+            }
+        }
+    }
+
+    /// Signed curvature at the parameter, positive for turning left (counter-clockwise
+    /// in y-up), by `(B' × B'') / |B'|^3`, and `(D × D') W^2 / |D|^3` for conic
+    /// with `B' = D / W^2` and `D' = N'' W - N W''`, in scaled derivatives.
+    fn curvature(&self, t: T) -> T {
+        let mt = T::ONE - t;
+        let (d1, d2, k) = match *self {
+            Self::Line(_) => return T::ZERO,
+            Self::Quad([p0, p1, p2]) => ((p1 - p0) * mt + (p2 - p1) * t, p2 - p1 * T::TWO + p0,
+                T::HALF),
+            Self::Cubic([p0, p1, p2, p3]) => {
+                let (a, b, c) = (p1 - p0, p2 - p1, p3 - p2);
+                (a * (mt * mt) + b * (T::TWO * mt * t) + c * (t * t), (b - a) * mt + (c - b) * t,
+                    T::TWO / T::from_i32(3))
+            }
+            Self::Conic([p0, p1, p2], w) => {   // halves of the derivatives, translated by p0
+                let (q1, q2) = (p1 - p0, p2 - p0);
+                let wt = mt * mt + T::TWO * w * mt * t + t * t;
+                let n = q1 * (T::TWO * w * mt * t) + q2 * (t * t);
+                let (dn, dw) = (q1 * (w * (mt - t)) + q2 * t, w * (mt - t) + t - mt);
+                (dn * wt - n * dw, (q2 - q1 * (T::TWO * w)) * wt - n * (T::TWO - T::TWO * w),
+                    wt * wt * T::HALF)
+            }
+        };
+        let len = d1.length();  if len <= T::ZERO { return T::ZERO }
+        (d1 / len).cross(d2 / len) / len * k
+    }
+
+    /** Parallel curve at the distance to the right side (rotated by -90 degrees from the
+        direction, i.e. outside of counter-clockwise contours in y-up), or to the left side
+        for negative distance, passed in order to the closure, a line for line, and cubics
+        within the tolerance for curves. Nothing for a segment of zero length.
+    ```
+        use ugl_rs::geometry::*;
+        let p = |x, y| Point::new(x, y);
+        let arc = Segment::Conic([p(100., 0.), p(100., 100.), p(0., 100.)],
+            core::f32::consts::FRAC_1_SQRT_2);  // quarter circle of radius 100
+        for d in [-50., 20.] {
+            let mut segs = Vec::new();  arc.offset(d, 0.01, &mut |seg| segs.push(seg));
+            assert_eq!(segs[0].start(), p(100. + d, 0.));
+            assert!((segs.last().unwrap().end() - p(0., 100. + d)).length() < 1e-4);
+            assert!(segs.iter().all(|seg| (1..10).all(|k|
+                (seg.eval(k as f32 / 10.).length() - (100. + d)).abs() <= 0.01)));
+        }
+
+        let mut segs = Vec::new();
+        Segment::Line([p(0., 0.), p(10., 0.)]).offset(2., 0.01, &mut |seg| segs.push(seg));
+        assert_eq!(segs, [Segment::Line([p(0., -2.), p(10., -2.)])]);
+    ``` */
+    pub fn offset(&self, distance: T, tolerance: T, f: &mut impl FnMut(Segment<T>)) {
+        if let Self::Line([p0, p1]) = *self {
+            if let Some(u) = (p1 - p0).normalize() {
+                let n = -u.perp() * distance;   f(Self::Line([p0 + n, p1 + n]))
+            }   return
+        }   self.offset_pieces(T::ZERO, T::ONE, distance, tolerance, 0, &mut |_, _, seg, _| f(seg))
+    }
+
+    /// Parallel cubics of the pieces in parameter ranges, and whether they're inverted
+    /// (the radius of curvature less than the distance on its side) at the ends.
+    fn offset_pieces(&self, t0: T, t1: T, d: T, tol: T, depth: u32,
+        f: &mut impl FnMut(T, T, Segment<T>, bool)) {
+        let normal = |t: T| self.tangent(t).normalize().map(|u| -u.perp() * d);
+        let (Some(n0), Some(n1)) = (normal(t0), normal(t1)) else { return };
+
+        //  handles scaled by `1 + d κ`, with the curvature at the ends of the cubic
+        //  `κ(0) = 2/3 (h0 × (c2 - c1)) / |h0|^3`, positive for turning left
+        let [p0, c1, c2, p3] = self.sub(t0, t1).to_cubic();
+        let scale = |h: Vector<T>, m: Vector<T>| {
+            let len = h.length();   if len <= T::ZERO { return T::ONE }
+            T::ONE + d * T::TWO / T::from_i32(3) * (h / len).cross(m / len) / len
+        };
+        let (h0, h1) = (c1 - p0, p3 - c2);
+        let (q0, q3) = (self.eval(t0) + n0, self.eval(t1) + n1);
+        let cubic = Segment::Cubic([q0, q0 + h0 * scale(h0, c2 - c1),
+            q3 - h1 * scale(h1, c1 - c2), q3]);
+
+        //  within the tolerance, and not across the inversion by the exact curvature
+        let quarter = T::HALF * T::HALF;
+        let ts = [quarter, T::HALF, T::ONE - quarter].map(|s| t0 + (t1 - t0) * s);
+        let inverted = [t0, ts[0], ts[1], ts[2], t1]
+            .map(|t| T::ONE + d * self.curvature(t) <= T::ZERO);
+        let fits = depth == MAX_DEPTH || inverted.iter().all(|&v| v == inverted[0]) &&
+            ts.into_iter().all(|t| normal(t)
+                .is_none_or(|n| cubic.nearest(self.eval(t) + n).1 <= tol));
+        if fits { f(t0, t1, cubic, inverted.contains(&true)) } else {
+            let tm = (t0 + t1) * T::HALF;
+            self.offset_pieces(t0, tm, d, tol, depth + 1, f);
+            self.offset_pieces(tm, t1, d, tol, depth + 1, f);
+        }
+    }
+}
+
+impl<T: Scalar> Path<T> {
+    /** Outset by positive distance, or inset by negative distance, the region filled by
+        the rule of the path, with the join (at the miter limit of 4) at convex corners,
+        and the parallel curves within the tolerance. It's the union of the region and
+        the stroke of its outline for outset, and the difference for inset, with the
+        outline oriented as the result of `boolean`.
+    ```
+        use ugl_rs::geometry::*;
+        let p = |x, y| Point::new(x, y);
+        let area = |path: &Path| path.flatten(1e-3).segments()
+            .map(|seg| seg.start().cross(seg.end())).sum::<f32>() / 2.;
+        let square = Path::rect(Rect::new(0., 0., 10., 10.));
+
+        let outset = square.offset(2., LineJoin::Miter, 0.01);
+        assert_eq!(outset.bounds(), Some(Rect::new(-2., -2., 14., 14.)));
+        let rounded = square.offset(2., LineJoin::Round, 0.01);
+        assert!((area(&rounded) - (100. + 4. * 20. + 4. * core::f32::consts::PI)).abs() < 0.1);
+        assert!((area(&square.offset(2., LineJoin::Bevel, 0.01)) - 188.).abs() < 0.1);
+        assert!((area(&square.offset(-2., LineJoin::Round, 0.01)) - 36.).abs() < 0.1);
+        assert!(square.offset(-5., LineJoin::Miter, 0.01).is_empty());
+
+        let circle = Path::circle(p(0., 0.), 10.);
+        let inset = circle.offset(-4., LineJoin::Miter, 0.01);
+        assert!(inset.segments().all(|seg| (seg.eval(0.5).length() - 6f32).abs() <= 0.01));
+        let ring = circle.boolean(&inset, BoolOp::Difference, 0.01);
+        assert!((area(&ring) - core::f32::consts::PI * (100. - 36.)).abs() < 0.1);
+        assert!(circle.offset(-15., LineJoin::Miter, 0.01).is_empty());     // no inverted one
+    ``` */
+    pub fn offset(&self, distance: T, join: LineJoin, tolerance: T) -> Self {
+        let clean = self.boolean(&Self::default(), BoolOp::Union, tolerance);
+        if distance == T::ZERO { return clean }
+        let (r, limit) = (distance.abs(), T::from_i32(4));
+        let dir = |seg: &Segment<T>, t: T| seg.tangent(t).normalize();
+        let right = |u: Vector<T>| -u.perp() * distance;
+
+        let mut pb = PathBuilder::new();
+        for (segs, _) in clean.contours() {
+            //  pieces with their parallel curves, and whether smoothly joined to the next,
+            //  the inverted parts of curves are flattened as lines with round joins,
+            //  since their parallel curves don't bound the regions swept by the normals
+            let mut pieces = Vec::new();
+            for seg in segs.iter().filter(|seg| dir(seg, T::ZERO).is_some()) {
+                let mut parts = Vec::new();
+                if let Segment::Line(_) = seg { parts.push((*seg, None)) } else {
+                    seg.offset_pieces(T::ZERO, T::ONE, distance, tolerance, 0,
+                        &mut |t0, t1, cubic, inverted| {
+                        let sub = seg.sub(t0, t1);
+                        if !inverted { parts.push((sub, Some(cubic)));   return }
+                        let mut last = sub.start();
+                        sub.flatten(tolerance, |p| if p != last {
+                            parts.push((Segment::Line([last, p]), None));   last = p;
+                        });
+                    });
+                }
+                let n = parts.len();
+                pieces.extend(parts.into_iter().enumerate().map(|(i, (s, o))| (s, o, i + 1 < n)));
+            }
+
+            let Some(&(first, off, _)) = pieces.first() else { continue };
+            pb.move_to(off.map_or_else(|| first.start() + right(dir(&first, T::ZERO).unwrap()),
+                |off| off.start()));
+            for (i, &(seg, off, smooth)) in pieces.iter().enumerate() {
+                match off { Some(off) => { pb.push_segment(off); }
+                    None => { pb.line_to(seg.end() + right(dir(&seg, T::ONE).unwrap())); } }
+                let next = &pieces[(i + 1) % pieces.len()].0;
+                let (ua, ub) = (dir(&seg, T::ONE).unwrap(), dir(next, T::ZERO).unwrap());
+                let (p, cos, sin) = (next.start(), ua.dot(ub), ua.cross(ub));
+                let (a, b) = (p + right(ua), p + right(ub));
+
+                if sin * distance <= T::ZERO {  // concave, or smooth within the tolerance
+                    if T::ZERO < cos && (b - a).length() <= tolerance { pb.line_to(b); }
+                    else { pb.line_to(p).line_to(b); }     continue
+                }
+
+                let cos_half = ((T::ONE + cos) * T::HALF).sqrt();
+                match if smooth { LineJoin::Round } else { join } {
+                    LineJoin::Bevel => { pb.line_to(b); }
+                    LineJoin::Miter | LineJoin::MiterClip if T::ONE <= cos_half * limit => {
+                        pb.line_to(p + (right(ua) + right(ub)) / (T::ONE + cos)).line_to(b); }
+                    LineJoin::Miter => { pb.line_to(b); }
+                    LineJoin::MiterClip => {
+                        let sin_half = ((T::ONE - cos) * T::HALF).sqrt();
+                        let t = r * (limit - cos_half) / sin_half;
+                        pb.line_to(a + ua * t).line_to(b - ub * t).line_to(b);
+                    }
+                    LineJoin::Round => {
+                        pb.arc_to(Vector::new(r, r), T::ZERO, false, T::ZERO < sin, b); }
+                }
+            }   pb.close();
+        }   pb.build().arrange(&Self::default(), tolerance, |w| 0 < w[0])
+    }
+}
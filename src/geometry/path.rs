@@ -0,0 +1,447 @@
+
+//  https://www.w3.org/TR/SVG/paths.html
+//  https://www.w3.org/TR/SVG/implnote.html#ArcImplementationNotes
+//  https://github.com/google/skia/blob/main/include/core/SkPath.h
+
+use crate::{geometry::{Point, Rect, Shape, Vector}, math::{Scalar, Transform2D}};
+
+/// Path verbs, stored compactly with the points they consume.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)] pub enum Verb {
+    /** 1 point */ Move, /** 1 point */ Line, /** 2 points */ Quad, /** 3 points */ Cubic,
+    /** 2 points and 1 weight */ Conic, /** 0 point */ Close,
+}
+
+/// Rules to determine the inside of a path.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)] pub enum FillRule {
+    /** winding number is not zero */ #[default] NonZero,
+    /** winding number is odd */ EvenOdd,
+}
+
+/// Path commands, with the points ending at the new current point.
+#[derive(Clone, Copy, Debug, PartialEq)] pub enum PathCmd<T: Scalar = f32> {
+    MoveTo(Point<T>), LineTo(Point<T>), QuadTo(Point<T>, Point<T>),
+    CubicTo(Point<T>, Point<T>, Point<T>),
+    /// rational quadratic Bézier by the control point, end point and the weight
+    ConicTo(Point<T>, Point<T>, T),
+    Close,
+}
+
+/// Curve segments with all control points (from the start point), see `Path::segments`.
+#[derive(Clone, Copy, Debug, PartialEq)] pub enum Segment<T: Scalar = f32> {
+    Line([Point<T>; 2]), Quad([Point<T>; 3]), Cubic([Point<T>; 4]), Conic([Point<T>; 3], T),
+}
+
+/** Free path of contours of lines and (quadratic/cubic/conic) Bézier curves,
+    build it by `PathBuilder`.
+```
+    use ugl_rs::{geometry::*, math::Transform2D};
+    let mut pb = PathBuilder::new();
+    pb.move_to(Point::new(0., 0.)).quad_to(Point::new(1., 2.), Point::new(2., 0.)).close();
+    pb.cubic_to(Point::new(0., -1.), Point::new(2., -1.), Point::new(2., 0.));
+    let path = pb.build();
+
+    assert_eq!(path.verbs(), [Verb::Move, Verb::Quad, Verb::Close, Verb::Move, Verb::Cubic]);
+    assert_eq!(path.iter().nth(3), Some(PathCmd::MoveTo(Point::new(0., 0.))));
+    assert_eq!(path.segments().count(), 3);     // including the closing line
+
+    assert_eq!(path.bounds(), Some(Rect::from_ltrb(0., -1., 2., 2.)));
+    assert_eq!(path.tight_bounds(), Some(Rect::from_ltrb(0., -0.75, 2., 1.)));
+    let moved = path.transform(&Transform2D::translation(1., 1.));
+    assert_eq!(moved.bounds(), Some(Rect::from_ltrb(1., 0., 3., 3.)));
+    assert_eq!(Path::<f32>::default().bounds(), None);
+
+    #[cfg(feature = "fixed")] {     use ugl_rs::math::{I16F16, Scalar};
+        let circle = Path::circle(Point::zero(), I16F16::from_i32(200));
+        let rc = circle.tight_bounds().unwrap();
+        assert!((rc.right.to_f32() - 200.).abs() < 0.01 && (rc.top.to_f32() + 200.).abs() < 0.01);
+    }
+``` */
+#[derive(Clone, Debug, Default, PartialEq)] pub struct Path<T: Scalar = f32> {
+    verbs: Vec<Verb>, points: Vec<Point<T>>, weights: Vec<T>,
+    pub fill_rule: FillRule,
+    pub(crate) shape: Option<Shape<T>>,
+}
+
+impl<T: Scalar> Path<T> {
+    #[inline] pub fn verbs  (&self) -> &[Verb] { &self.verbs }
+    #[inline] pub fn points (&self) -> &[Point<T>] { &self.points }
+    #[inline] pub fn weights(&self) -> &[T] { &self.weights }
+    #[inline] pub fn is_empty(&self) -> bool { self.verbs.is_empty() }
+    /// The analytic shape if it's constructed from one, for the fast paths of rasterizer.
+    #[inline] pub fn shape  (&self) -> Option<&Shape<T>> { self.shape.as_ref() }
+
+    pub fn iter(&self) -> impl Iterator<Item = PathCmd<T>> + '_ {
+        let (mut pts, mut wts) = (self.points.iter().copied(), self.weights.iter().copied());
+        let mut pt = move || pts.next().unwrap();
+        self.verbs.iter().map(move |verb| match verb {
+            Verb::Move  => PathCmd::MoveTo(pt()),
+            Verb::Line  => PathCmd::LineTo(pt()),
+            Verb::Quad  => PathCmd::QuadTo(pt(), pt()),
+            Verb::Cubic => PathCmd::CubicTo(pt(), pt(), pt()),
+            Verb::Conic => PathCmd::ConicTo(pt(), pt(), wts.next().unwrap()),
+            Verb::Close => PathCmd::Close,
+        })
+    }
+
+    /// Iterate curve segments, with the closing line (if not zero length) of closed contours.
+    pub fn segments(&self) -> impl Iterator<Item = Segment<T>> + '_ {
+        let (mut start, mut last) = (Point::zero(), Point::zero());
+        self.iter().filter_map(move |cmd| {
+            let (seg, end) = match cmd {
+                PathCmd::MoveTo(p) => { (start, last) = (p, p);   return None }
+                PathCmd::LineTo(p) => (Segment::Line([last, p]), p),
+                PathCmd::QuadTo(c, p) => (Segment::Quad([last, c, p]), p),
+                PathCmd::CubicTo(c1, c2, p) => (Segment::Cubic([last, c1, c2, p]), p),
+                PathCmd::ConicTo(c, p, w) => (Segment::Conic([last, c, p], w), p),
+                PathCmd::Close => if last == start { return None } else {
+                    (Segment::Line([last, start]), start) },
+            };  last = end;     Some(seg)
+        })
+    }
+
+    /// Segments of each contour (the closing line included if not zero length) and whether
+    /// it's closed, contours without segments are skipped.
+    pub(crate) fn contours(&self) -> Vec<(Vec<Segment<T>>, bool)> {
+        let (mut contours, mut segs) = (Vec::new(), Vec::new());
+        let (mut start, mut last) = (Point::zero(), Point::zero());
+        let mut finish = |segs: &mut Vec<_>, closed|
+            if !segs.is_empty() { contours.push((core::mem::take(segs), closed)) };
+
+        for cmd in self.iter() {
+            let (seg, end) = match cmd {
+                PathCmd::MoveTo(p) => {
+                    finish(&mut segs, false);   (start, last) = (p, p);     continue
+                }
+                PathCmd::LineTo(p) => (Segment::Line([last, p]), p),
+                PathCmd::QuadTo(c, p) => (Segment::Quad([last, c, p]), p),
+                PathCmd::CubicTo(c1, c2, p) => (Segment::Cubic([last, c1, c2, p]), p),
+                PathCmd::ConicTo(c, p, w) => (Segment::Conic([last, c, p], w), p),
+                PathCmd::Close => {
+                    if last != start { segs.push(Segment::Line([last, start])) }
+                    finish(&mut segs, true);    last = start;   continue
+                }
+            };  segs.push(seg);     last = end;
+        }   finish(&mut segs, false);   contours
+    }
+
+    /// Transform all points, the weights of conics are kept as invariant of affine transform.
+    pub fn transform(&self, ts: &Transform2D<T>) -> Self {
+        Self { verbs: self.verbs.clone(), weights: self.weights.clone(), fill_rule: self.fill_rule,
+            points: self.points.iter().map(|pt| ts.map_point(*pt)).collect(),
+            shape: self.shape.and_then(|shape| shape.transform(ts)) }
+    }
+
+    /// Bounds of all points including control points, `None` for empty path.
+    pub fn bounds(&self) -> Option<Rect<T>> { Rect::from_points(&self.points) }
+
+    /// Bounds of the path's geometry: the end points and the extrema of curves.
+    pub fn tight_bounds(&self) -> Option<Rect<T>> {
+        let mut pts = Vec::new();
+        self.iter().for_each(|cmd| if let PathCmd::MoveTo(p) = cmd { pts.push(p) });
+        self.segments().for_each(|seg| {
+            pts.push(seg.start());  pts.push(seg.end());
+            seg.extrema(&mut |t| pts.push(seg.eval(t)));
+        }); Rect::from_points(&pts)
+    }
+}
+
+impl<T: Scalar> Segment<T> {
+    #[inline] pub fn start(&self) -> Point<T> {
+        match self { Self::Line(p) => p[0], Self::Quad(p) | Self::Conic(p, _) => p[0],
+            Self::Cubic(p) => p[0] }
+    }
+    #[inline] pub fn end  (&self) -> Point<T> {
+        match self { Self::Line(p) => p[1], Self::Quad(p) | Self::Conic(p, _) => p[2],
+            Self::Cubic(p) => p[3] }
+    }
+
+    /// Point at the parameter t in [0, 1].
+    pub fn eval(&self, t: T) -> Point<T> {
+        let mt = T::ONE - t;
+        match self {
+            Self::Line(p) => p[0].lerp(p[1], t),
+            Self::Quad(p) => p[0] * (mt * mt) + p[1] * (T::TWO * mt * t) + p[2] * (t * t),
+            Self::Cubic(p) => {
+                let (a, b) = (mt * mt, t * t);
+                p[0] * (a * mt) + p[1] * (a * t * T::from_i32(3)) +
+                    p[2] * (mt * b * T::from_i32(3)) + p[3] * (b * t)
+            }
+            Self::Conic(p, w) => {
+                let (a, b, c) = (mt * mt, T::TWO * *w * mt * t, t * t);
+                (p[0] * a + p[1] * b + p[2] * c) / (a + b + c)
+            }
+        }
+    }
+
+    /** Split at the parameter into two segments by de Casteljau's algorithm,
+        in homogeneous coordinates for conic.
+    ```
+        use ugl_rs::geometry::*;
+        let p = |x, y| Point::new(x, y);
+        let conic = Segment::Conic([p(1., 0.), p(1., 1.), p(0., 1.)], 0.5f32.sqrt());
+        let (a, b) = conic.split(0.3);
+        assert!((a.end() - conic.eval(0.3)).length() < 1e-6 && a.end() == b.start());
+        assert!([a.eval(0.5), b.eval(0.5)].iter().all(|pt| (pt.length() - 1f32).abs() < 1e-6));
+
+        let cubic = Segment::Cubic([p(0., 0.), p(0., 3.), p(3., 3.), p(3., 0.)]);
+        let sub = cubic.sub(0.8, 0.2);  // reversed
+        assert!((sub.start() - cubic.eval(0.8)).length() < 1e-6);
+        assert!((sub.eval(0.5) - cubic.eval(0.5)).length() < 1e-6);
+        let sub = conic.sub(0.25, 0.75);    // in the parameters of the whole
+        assert!((sub.start() - conic.eval(0.25)).length() < 1e-6);
+        assert!((sub.eval(0.5) - conic.eval(0.5)).length() < 1e-6);
+    ``` */
+    pub fn split(&self, t: T) -> (Self, Self) {
+        let lerp = |a: Point<T>, b: Point<T>| a.lerp(b, t);
+        match *self {
+            Self::Line([p0, p1]) => {
+                let m = lerp(p0, p1);   (Self::Line([p0, m]), Self::Line([m, p1]))
+            }
+            Self::Quad([p0, p1, p2]) => {
+                let (a, b) = (lerp(p0, p1), lerp(p1, p2));  let m = lerp(a, b);
+                (Self::Quad([p0, a, m]), Self::Quad([m, b, p2]))
+            }
+            Self::Cubic([p0, p1, p2, p3]) => {
+                let (a, b, c) = (lerp(p0, p1), lerp(p1, p2), lerp(p2, p3));
+                let (d, e) = (lerp(a, b), lerp(b, c));  let m = lerp(d, e);
+                (Self::Cubic([p0, a, d, m]), Self::Cubic([m, e, c, p3]))
+            }
+            Self::Conic([p0, p1, p2], w) => {   // (p0, 1), (p1 * w, w), (p2, 1)
+                let (wa, wb) = (T::ONE.lerp(w, t), w.lerp(T::ONE, t));
+                let (pa, pb) = (lerp(p0, p1 * w), (p1 * w).lerp(p2, t));
+                let wm = wa.lerp(wb, t);    let m = pa.lerp(pb, t) / wm;
+                let s = wm.sqrt();  // normalized to have weights 1 at both ends
+                (Self::Conic([p0, pa / wa, m], wa / s), Self::Conic([m, pb / wb, p2], wb / s))
+            }
+        }
+    }
+
+    /// Part of the segment in the parameter range, reversed if `t1 < t0`.
+    pub fn sub(&self, t0: T, t1: T) -> Self {
+        if t1 < t0 { return self.sub(t1, t0).reversed() }
+        let left = if t1 < T::ONE { self.split(t1).0 } else { *self };
+        if t0 <= T::ZERO { return left }
+        let mut t = t0 / t1.min(T::ONE);
+        if let (Self::Conic(_, w), true) = (self, t1 < T::ONE) {
+            //  parameter in the left part reparameterized by the normalization of `split`,
+            //  `s = ρ u / (1 - u + ρ u)` with `ρ = 1 / sqrt(W(t1))`
+            let mt = T::ONE - t1;
+            let rho = T::ONE / (mt * mt + T::TWO * *w * mt * t1 + t1 * t1).sqrt();
+            t = t / (t + rho * (T::ONE - t));
+        }   left.split(t).1
+    }
+
+    pub fn reversed(&self) -> Self {
+        match *self {
+            Self::Line([p0, p1]) => Self::Line([p1, p0]),
+            Self::Quad([p0, p1, p2]) => Self::Quad([p2, p1, p0]),
+            Self::Cubic([p0, p1, p2, p3]) => Self::Cubic([p3, p2, p1, p0]),
+            Self::Conic([p0, p1, p2], w) => Self::Conic([p2, p1, p0], w),
+        }
+    }
+
+    /// Call back with parameters in (0, 1) where the derivative of x or y is zero.
+    pub(crate) fn extrema(&self, f: &mut impl FnMut(T)) {
+        let axes = |p: &[Point<T>], coef: &dyn Fn(&[T]) -> [T; 3], f: &mut dyn FnMut(T)| {
+            for axis in [0, 1] {
+                let v = p.iter().map(|p| if axis == 0 { p.x } else { p.y }).collect::<Vec<_>>();
+                let [a, b, c] = coef(&v);   solve_quadratic(a, b, c, &mut *f);
+            }
+        };
+
+        match self {
+            Self::Line(_) => (),
+            Self::Quad(p) => axes(p, &|v| [T::ZERO, v[0] - v[1] * T::TWO + v[2], v[1] - v[0]], f),
+            Self::Cubic(p) => axes(p, &|v| {    // B'(t) / 3
+                let (a, b, c) = (v[1] - v[0], v[2] - v[1], v[3] - v[2]);
+                [a - b * T::TWO + c, (b - a) * T::TWO, a]
+            }, f),
+            Self::Conic(p, w) => { let w = *w;
+                axes(p, &|v| {   // numerator of the derivative of the rational function
+                    let (p20, wp10) = (v[2] - v[0], (v[1] - v[0]) * w);
+                    [p20 * w - p20, p20 - wp10 * T::TWO, wp10]
+                }, f)
+            }
+        }
+    }
+}
+
+/// Call back with roots in (0, 1) of `a t^2 + b t + c = 0`.
+fn solve_quadratic<T: Scalar>(a: T, b: T, c: T, f: &mut dyn FnMut(T)) {
+    let mut root = |t: T| if T::ZERO < t && t < T::ONE { f(t) };
+    //  normalized by the maximum magnitude to avoid overflow of squares for fixed-point
+    let m = a.abs().max(b.abs()).max(c.abs());  if m <= T::ZERO { return }
+    let (a, b, c) = (a / m, b / m, c / m);
+    if a.abs() <= T::EPSILON {
+        if T::EPSILON < b.abs() { root(-c / b) }    return
+    }
+
+    let d = b * b - T::from_i32(4) * a * c;
+    if d < T::ZERO { return }   let d = d.sqrt();
+    //  avoid the cancellation: https://people.csail.mit.edu/bkph/articles/Quadratics.pdf
+    let q = if b < T::ZERO { (d - b) * T::HALF } else { -(b + d) * T::HALF };
+    root(q / a);    if T::EPSILON < q.abs() { root(c / q) }
+}
+
+/** Builder of `Path`, drawing commands can be chained, and relative variants
+    are relative to the current point. A drawing command without a preceding `move_to`
+    starts the contour at the current point (the start of last contour after `close`).
+```
+    use ugl_rs::geometry::*;
+    let mut pb = PathBuilder::new();
+    pb.move_to(Point::new(10., 10.)).rel_line_to(Vector::new(10., 0.))
+      .rel_quad_to(Vector::new(5., 5.), Vector::new(0., 10.)).close()
+      .rel_line_to(Vector::new(0., -5.));
+    let path = pb.fill_rule(FillRule::EvenOdd).build();
+    assert_eq!(path.points().last(), Some(&Point::new(10., 5.)));
+    assert_eq!(path.fill_rule, FillRule::EvenOdd);
+
+    let mut pb = PathBuilder::new();        // semicircle from (0, 0) to (2, 0) through (1, -1)
+    pb.move_to(Point::new(0f32, 0.))
+      .arc_to(Vector::new(1., 1.), 0., false, true, Point::new(2., 0.));
+    let path = pb.build();
+    assert_eq!(path.verbs(), [Verb::Move, Verb::Conic, Verb::Conic]);
+    let rc = path.tight_bounds().unwrap();
+    assert!([rc.left, rc.top + 1., rc.right - 2., rc.bottom].iter().all(|v| v.abs() < 1e-6));
+    assert!(path.segments().all(|seg|
+        ((seg.eval(0.3) - Point::new(1., 0.)).length() - 1.).abs() < 1e-6));
+``` */
+#[derive(Clone, Debug, Default)] pub struct PathBuilder<T: Scalar = f32> {
+    path: Path<T>, start: Point<T>, last: Point<T>, need_move: bool,
+}
+
+impl<T: Scalar> PathBuilder<T> {
+    pub fn new() -> Self { Self { need_move: true, ..Default::default() } }
+
+    pub fn fill_rule(&mut self, rule: FillRule) -> &mut Self { self.path.fill_rule = rule; self }
+    /// Take the built path, and reset the builder.
+    pub fn build(&mut self) -> Path<T> { core::mem::replace(self, Self::new()).path }
+
+    /// The current point, which the next drawing command starts from.
+    #[inline] pub fn current_point(&self) -> Point<T> { self.last }
+
+    fn push(&mut self, verb: Verb, pts: &[Point<T>]) -> &mut Self {
+        if self.need_move { let pt = self.last; self.move_to(pt); }
+        self.path.verbs.push(verb);     self.path.points.extend_from_slice(pts);
+        self.last = *pts.last().unwrap();   self
+    }
+
+    pub fn move_to(&mut self, p: Point<T>) -> &mut Self {
+        if self.path.verbs.last() == Some(&Verb::Move) {    // replace the lone move
+            *self.path.points.last_mut().unwrap() = p;
+        } else { self.path.verbs.push(Verb::Move);  self.path.points.push(p); }
+        (self.start, self.last, self.need_move) = (p, p, false);    self
+    }
+    pub fn line_to(&mut self, p: Point<T>) -> &mut Self { self.push(Verb::Line, &[p]) }
+    pub fn quad_to(&mut self, c: Point<T>, p: Point<T>) -> &mut Self {
+        self.push(Verb::Quad, &[c, p])
+    }
+    pub fn cubic_to(&mut self, c1: Point<T>, c2: Point<T>, p: Point<T>) -> &mut Self {
+        self.push(Verb::Cubic, &[c1, c2, p])
+    }
+    /// Rational quadratic Bézier, an elliptical arc for weight in (0, 1),
+    /// a parabola for weight 1 (as `quad_to`), and a hyperbola for weight > 1.
+    pub fn conic_to(&mut self, c: Point<T>, p: Point<T>, w: T) -> &mut Self {
+        self.push(Verb::Conic, &[c, p]);    self.path.weights.push(w);  self
+    }
+
+    /** Elliptical arc to the end point as SVG `A` command, by the radii, rotation of the
+        x-axis in radians, and flags to choose one of the four candidate arcs.
+        It's approximated exactly by conics of at most 90 degrees; out-of-range radii
+        are corrected as SVG specification: a straight line for zero radius,
+        and scaled up if too small to reach the end point. */
+    pub fn arc_to(&mut self, radii: Vector<T>, x_rotation: T, large_arc: bool, sweep: bool,
+        p: Point<T>) -> &mut Self {
+        let p0 = self.last;
+        if p0 == p { return self }
+        let (mut rx, mut ry) = (radii.x.abs(), radii.y.abs());
+        if rx <= T::EPSILON || ry <= T::EPSILON { return self.line_to(p) }
+
+        //  F.6.5: conversion from endpoint to center parameterization
+        let rot = Transform2D::rotation(x_rotation);
+        let p1 = rot.invert().unwrap().map_vector((p0 - p) * T::HALF);
+        let lambda = (p1.x * p1.x) / (rx * rx) + (p1.y * p1.y) / (ry * ry);
+        if T::ONE < lambda { let s = lambda.sqrt(); rx *= s;   ry *= s; }
+
+        let (rx2, ry2) = (rx * rx, ry * ry);
+        let (nx, ny) = (rx2 * p1.y * p1.y, ry2 * p1.x * p1.x);
+        let mut coef = ((rx2 * ry2 - nx - ny) / (nx + ny)).max(T::ZERO).sqrt();
+        if large_arc == sweep { coef = -coef }
+        let c1 = Point::new(coef * rx * p1.y / ry, -coef * ry * p1.x / rx);
+
+        let angle = |v: Vector<T>| v.y.atan2(v.x);
+        let theta = angle(Vector::new((p1.x - c1.x) / rx, (p1.y - c1.y) / ry));
+        let mut delta = angle(Vector::new((-p1.x - c1.x) / rx, (-p1.y - c1.y) / ry)) - theta;
+        let tau = T::from_f64(core::f64::consts::TAU);
+        if sweep && delta < T::ZERO { delta += tau }
+        else if !sweep && T::ZERO < delta { delta -= tau }
+
+        //  the unit circle mapped to the ellipse
+        let center = rot.map_vector(c1) + p0.mid(p);
+        let ts = Transform2D::translation(center.x, center.y).pre_rotate(x_rotation)
+            .pre_scale(rx, ry);
+
+        let n = (delta.abs() / T::from_f64(core::f64::consts::FRAC_PI_2) - T::from_f32(1e-3))
+            .ceil().max(T::ONE);
+        let (step, count) = (delta / n, n.to_i32());
+        let (_, w) = (step * T::HALF).sin_cos();
+        for i in 1..=count {
+            let a = theta + step * T::from_i32(i);
+            let (sm, cm) = (a - step * T::HALF).sin_cos();
+            let c = ts.map_point(Point::new(cm / w, sm / w));
+            let end = if i == count { p } else {
+                let (s, c) = a.sin_cos();     ts.map_point(Point::new(c, s)) };
+            self.conic_to(c, end, w);
+        }   self
+    }
+
+    pub fn close(&mut self) -> &mut Self {
+        if !self.need_move { self.path.verbs.push(Verb::Close) }
+        (self.last, self.need_move) = (self.start, true);   self
+    }
+
+    pub fn rel_move_to (&mut self, v: Vector<T>) -> &mut Self { self.move_to(self.last + v) }
+    pub fn rel_line_to (&mut self, v: Vector<T>) -> &mut Self { self.line_to(self.last + v) }
+    pub fn rel_quad_to (&mut self, c: Vector<T>, v: Vector<T>) -> &mut Self {
+        let o = self.last;  self.quad_to(o + c, o + v)
+    }
+    pub fn rel_cubic_to(&mut self, c1: Vector<T>, c2: Vector<T>, v: Vector<T>) -> &mut Self {
+        let o = self.last;  self.cubic_to(o + c1, o + c2, o + v)
+    }
+    pub fn rel_conic_to(&mut self, c: Vector<T>, v: Vector<T>, w: T) -> &mut Self {
+        let o = self.last;  self.conic_to(o + c, o + v, w)
+    }
+    pub fn rel_arc_to(&mut self, radii: Vector<T>, x_rotation: T, large_arc: bool, sweep: bool,
+        v: Vector<T>) -> &mut Self {
+        let o = self.last;  self.arc_to(radii, x_rotation, large_arc, sweep, o + v)
+    }
+
+    /// Append the command.
+    pub fn push_cmd(&mut self, cmd: PathCmd<T>) -> &mut Self {
+        match cmd {
+            PathCmd::MoveTo(p) => self.move_to(p),
+            PathCmd::LineTo(p) => self.line_to(p),
+            PathCmd::QuadTo(c, p) => self.quad_to(c, p),
+            PathCmd::CubicTo(c1, c2, p) => self.cubic_to(c1, c2, p),
+            PathCmd::ConicTo(c, p, w) => self.conic_to(c, p, w),
+            PathCmd::Close => self.close(),
+        }
+    }
+
+    /// Append the segment from the current point, its start point is ignored.
+    pub(crate) fn push_segment(&mut self, seg: Segment<T>) -> &mut Self {
+        match seg {
+            Segment::Line([_, p]) => self.line_to(p),
+            Segment::Quad([_, c, p]) => self.quad_to(c, p),
+            Segment::Cubic([_, c1, c2, p]) => self.cubic_to(c1, c2, p),
+            Segment::Conic([_, c, p], w) => self.conic_to(c, p, w),
+        }
+    }
+}
+
+impl<T: Scalar> FromIterator<PathCmd<T>> for Path<T> {
+    fn from_iter<I: IntoIterator<Item = PathCmd<T>>>(iter: I) -> Self {
+        let mut pb = PathBuilder::new();
+        iter.into_iter().for_each(|cmd| { pb.push_cmd(cmd); });     pb.build()
+    }
+}
@@ -0,0 +1,211 @@
+
+use core::ops::{Add, Sub};
+use crate::{geometry::Point, math::{Scalar, Transform2D}};
+
+/** Axis-aligned rectangle covering [left, right) x [top, bottom), generic over
+    the coordinate type, in pixels for `IRect`.
+```
+    use ugl_rs::{geometry::*, math::Transform2D};
+    let (a, b) = (IRect::new(0, 0, 10, 10), IRect::new(5, -5, 10, 10));
+    assert_eq!(a.intersect(&b), Some(IRect::from_ltrb(5, 0, 10, 5)));
+    assert_eq!(a.union(&b), IRect::from_ltrb(0, -5, 15, 10));
+    assert_eq!(a.intersect(&IRect::new(10, 0, 5, 5)), None);
+    assert!(a.contains(9, 0) && !a.contains(10, 0));
+    assert!(a.contains_rect(&a.inset(2, 3)) && a.outset(1, 1).contains_rect(&a));
+
+    let rc = Rect::new(0.5, -0.5, 2., 1.);
+    assert_eq!(rc.round_out(), IRect::from_ltrb(0, -1, 3, 1));
+    assert_eq!(rc.round(), IRect::from_ltrb(1, -1, 3, 1));      // round half away from zero
+    assert_eq!(rc.center(), Point::new(1.5, 0.));
+
+    let ts = Transform2D::rotation(core::f32::consts::FRAC_PI_4);
+    let rc = Rect::new(0., 0., 1., 1.).transform(&ts);
+    assert!((rc.width() - 2f32.sqrt()).abs() < 1e-6 && (rc.top).abs() < 1e-6);
+    assert_eq!(Rect::from_points(&[Point::new(1., 2.), Point::new(-1., 0.)]),
+        Some(Rect::from_ltrb(-1., 0., 1., 2.)));
+    assert_eq!(IRect::new(1, 2, 3, 4).to_rect(), Rect::new(1., 2., 3., 4.));
+``` */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)] pub struct Rect<T = f32> {
+    pub left: T, pub top: T, pub right: T, pub bottom: T,
+}
+
+/// Integer rectangle in pixels.
+pub type IRect = Rect<i32>;
+
+#[inline] fn min<T: PartialOrd>(a: T, b: T) -> T { if b < a { b } else { a } }
+#[inline] fn max<T: PartialOrd>(a: T, b: T) -> T { if a < b { b } else { a } }
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Rect<T> {
+    #[inline] pub fn new(x: T, y: T, w: T, h: T) -> Self {
+        Self { left: x, top: y, right: x + w, bottom: y + h }
+    }
+    #[inline] pub fn from_ltrb(left: T, top: T, right: T, bottom: T) -> Self {
+        Self { left, top, right, bottom }
+    }
+
+    #[inline] pub fn width (&self) -> T { self.right  - self.left }
+    #[inline] pub fn height(&self) -> T { self.bottom - self.top  }
+    #[inline] pub fn is_empty(&self) -> bool { !(self.left < self.right && self.top < self.bottom) }
+
+    /// The overlapped part, `None` if not overlapped.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        let rect = Self { left: max(self.left, other.left), top: max(self.top, other.top),
+            right: min(self.right, other.right), bottom: min(self.bottom, other.bottom) };
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /// The bounding rectangle of both, ignoring the empty one.
+    pub fn union(&self, other: &Self) -> Self {
+        if other.is_empty() { return *self } else if self.is_empty() { return *other }
+        Self { left: min(self.left, other.left), top: min(self.top, other.top),
+            right: max(self.right, other.right), bottom: max(self.bottom, other.bottom) }
+    }
+
+    #[inline] pub fn contains(&self, x: T, y: T) -> bool {
+        self.left <= x && x < self.right && self.top <= y && y < self.bottom
+    }
+    /// Whether `other` is inside, always false for empty rectangle.
+    #[inline] pub fn contains_rect(&self, other: &Self) -> bool {
+        !other.is_empty() && self.left <= other.left && other.right <= self.right &&
+            self.top <= other.top && other.bottom <= self.bottom
+    }
+
+    /// Shrink by `dx` on left/right and `dy` on top/bottom, enlarge if negative.
+    #[inline] pub fn inset (&self, dx: T, dy: T) -> Self {
+        Self::from_ltrb(self.left + dx, self.top + dy, self.right - dx, self.bottom - dy)
+    }
+    #[inline] pub fn outset(&self, dx: T, dy: T) -> Self {
+        Self::from_ltrb(self.left - dx, self.top - dy, self.right + dx, self.bottom + dy)
+    }
+
+    #[inline] pub fn translate(&self, dx: T, dy: T) -> Self {
+        Self::from_ltrb(self.left + dx, self.top + dy, self.right + dx, self.bottom + dy)
+    }
+}
+
+impl<T: Scalar> Rect<T> {
+    /// The bounds of the points, `None` if there is no point.
+    pub fn from_points(pts: &[Point<T>]) -> Option<Self> {
+        let (first, rest) = pts.split_first()?;
+        Some(rest.iter().fold(Self::from_ltrb(first.x, first.y, first.x, first.y), |rc, pt|
+            Self::from_ltrb(rc.left.min(pt.x), rc.top.min(pt.y),
+                rc.right.max(pt.x), rc.bottom.max(pt.y))))
+    }
+
+    #[inline] pub fn center(&self) -> Point<T> {
+        Point::new(self.left + self.right, self.top + self.bottom) * T::HALF
+    }
+
+    /// The smallest pixel bounds covering it.
+    pub fn round_out(&self) -> IRect {
+        IRect::from_ltrb(self.left.floor().to_i32(), self.top.floor().to_i32(),
+            self.right.ceil().to_i32(), self.bottom.ceil().to_i32())
+    }
+    /// Round the edges to the nearest pixel boundaries.
+    pub fn round(&self) -> IRect {
+        IRect::from_ltrb(self.left.round().to_i32(), self.top.round().to_i32(),
+            self.right.round().to_i32(), self.bottom.round().to_i32())
+    }
+
+    /// The bounds of the transformed rectangle.
+    pub fn transform(&self, ts: &Transform2D<T>) -> Self {
+        let corners = ts.map_rect(Point::new(self.left, self.top),
+            Point::new(self.right, self.bottom));
+        Self::from_points(&corners).unwrap()
+    }
+}
+
+impl IRect {
+    #[inline] pub fn to_rect<T: Scalar>(&self) -> Rect<T> {
+        Rect::from_ltrb(T::from_i32(self.left), T::from_i32(self.top),
+            T::from_i32(self.right), T::from_i32(self.bottom))
+    }
+}
+
+/** Set of non-overlapping integer rectangles, e.g. for damage tracking of dirty areas.
+```
+    use ugl_rs::geometry::{IRect, Region};
+    let mut rgn = Region::from(IRect::new(0, 0, 10, 10));
+    rgn.union(IRect::new(5, 5, 10, 10));
+    assert_eq!(rgn.area(), 175);
+    assert_eq!(rgn.bounds(), IRect::new(0, 0, 15, 15));
+    assert!(rgn.contains(12, 12) && !rgn.contains(12, 2));
+
+    rgn.subtract(IRect::new(4, 4, 4, 4));
+    assert_eq!(rgn.area(), 159);
+    assert!(!rgn.contains(5, 5) && rgn.contains(8, 8));
+    assert!(rgn.iter().all(|a| rgn.iter().all(|b| a == b || a.intersect(b).is_none())));
+
+    let clip = rgn.intersect(IRect::new(0, 0, 5, 5));
+    assert_eq!(clip.area(), 24);
+    rgn.subtract_region(&Region::from(rgn.bounds()));
+    assert!(rgn.is_empty());
+``` */
+#[derive(Clone, Debug, Default, PartialEq, Eq)] pub struct Region { rects: Vec<IRect> }
+
+impl From<IRect> for Region {
+    fn from(rect: IRect) -> Self {
+        Self { rects: if rect.is_empty() { vec![] } else { vec![rect] } }
+    }
+}
+
+/// Split the part of `a` outside of `b` into up to 4 rectangles.
+fn split(a: &IRect, b: &IRect, out: &mut Vec<IRect>) {
+    let Some(c) = a.intersect(b) else { out.push(*a); return };
+    [IRect::from_ltrb(a.left, a.top, a.right, c.top),
+     IRect::from_ltrb(a.left, c.top, c.left, c.bottom),
+     IRect::from_ltrb(c.right, c.top, a.right, c.bottom),
+     IRect::from_ltrb(a.left, c.bottom, a.right, a.bottom)]
+        .into_iter().filter(|rc| !rc.is_empty()).for_each(|rc| out.push(rc))
+}
+
+impl Region {
+    #[inline] pub fn new() -> Self { Self::default() }
+    #[inline] pub fn is_empty(&self) -> bool { self.rects.is_empty() }
+    #[inline] pub fn clear(&mut self) { self.rects.clear() }
+    #[inline] pub fn iter(&self) -> core::slice::Iter<'_, IRect> { self.rects.iter() }
+
+    pub fn bounds(&self) -> IRect {
+        self.rects.iter().fold(IRect::default(), |bounds, rc| bounds.union(rc))
+    }
+    pub fn area(&self) -> i64 {
+        self.rects.iter().map(|rc| rc.width() as i64 * rc.height() as i64).sum()
+    }
+    pub fn contains(&self, x: i32, y: i32) -> bool { self.rects.iter().any(|rc| rc.contains(x, y)) }
+
+    /// Add the rectangle, rectangles covered by it are dropped,
+    /// and only its parts not covered yet are stored.
+    pub fn union(&mut self, rect: IRect) {
+        if rect.is_empty() { return }
+        self.rects.retain(|rc| !rect.contains_rect(rc));
+
+        let mut parts = vec![rect];
+        for rc in &self.rects {
+            let mut rest = Vec::with_capacity(parts.len());
+            parts.iter().for_each(|p| split(p, rc, &mut rest));     parts = rest;
+            if parts.is_empty() { return }
+        }   self.rects.extend(parts)
+    }
+
+    /// Remove the area of the rectangle.
+    pub fn subtract(&mut self, rect: IRect) {
+        if rect.is_empty() { return }
+        let mut rects = Vec::with_capacity(self.rects.len());
+        self.rects.iter().for_each(|rc| split(rc, &rect, &mut rects));    self.rects = rects;
+    }
+
+    /// The part within the rectangle.
+    pub fn intersect(&self, rect: IRect) -> Self {
+        Self { rects: self.rects.iter().filter_map(|rc| rc.intersect(&rect)).collect() }
+    }
+
+    pub fn union_region   (&mut self, other: &Self) { other.iter().for_each(|rc| self.union(*rc)) }
+    pub fn subtract_region(&mut self, other: &Self) {
+        other.iter().for_each(|rc| self.subtract(*rc))
+    }
+}
+
+impl<'a> IntoIterator for &'a Region {
+    type Item = &'a IRect;  type IntoIter = core::slice::Iter<'a, IRect>;
+    fn into_iter(self) -> Self::IntoIter { self.rects.iter() }
+}
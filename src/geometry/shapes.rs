@@ -0,0 +1,252 @@
+
+//  https://www.w3.org/TR/SVG/shapes.html
+//  https://drafts.csswg.org/css-backgrounds/#corner-overlap
+//  https://spencermortensen.com/articles/bezier-circle/
+//  All shapes are drawn clockwise in y-down coordinates, elliptical arcs are exact conics
+//  by default, and the rectangles/ellipses are marked with `Shape` in the built paths.
+
+use core::f64::consts::{FRAC_PI_2, TAU};
+use crate::{geometry::{Path, PathBuilder, Point, Rect, Vector}, math::{Scalar, Transform2D}};
+
+/// Analytic shapes that paths are constructed from.
+#[derive(Clone, Copy, Debug, PartialEq)] pub enum Shape<T: Scalar = f32> {
+    Rect(Rect<T>),
+    /// radii of corners in order of top-left, top-right, bottom-right and bottom-left
+    RoundRect(Rect<T>, [Vector<T>; 4]),
+    /// center and radii
+    Ellipse(Point<T>, Vector<T>),
+}
+
+impl<T: Scalar> Shape<T> {
+    /// Shape kept only by positive scaling and translation, `None` otherwise.
+    pub fn transform(&self, ts: &Transform2D<T>) -> Option<Self> {
+        if ts.b != T::ZERO || ts.c != T::ZERO || ts.a <= T::ZERO || ts.d <= T::ZERO {
+            return None
+        }
+        let scale = |v: Vector<T>| Vector::new(v.x * ts.a, v.y * ts.d);
+        Some(match self {
+            Self::Rect(rc) => Self::Rect(rc.transform(ts)),
+            Self::RoundRect(rc, radii) => Self::RoundRect(rc.transform(ts), radii.map(scale)),
+            Self::Ellipse(center, radii) => Self::Ellipse(ts.map_point(*center), scale(*radii)),
+        })
+    }
+}
+
+/// How to close the arc.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)] pub enum ArcKind {
+    #[default] Open,
+    /** closed by the chord */ Chord,
+    /** closed by lines through the center */ Pie,
+}
+
+//  magic number to approximate a quarter of circle by cubic, with radial error 0.019%
+const CUBIC_ARC_K: f64 = 0.551_915_024_494;
+
+impl<T: Scalar> Path<T> {
+    fn with_shape(mut pb: PathBuilder<T>, shape: Shape<T>) -> Self {
+        let mut path = pb.build();  path.shape = Some(shape);   path
+    }
+
+    /** Rectangle starting from the top-left corner.
+    ```
+        use ugl_rs::{geometry::*, math::Transform2D};
+        let rc = Rect::new(1., 2., 3., 4.);     let path = Path::rect(rc);
+        assert_eq!(path.points(), &[Point::new(1., 2.), Point::new(4., 2.),
+            Point::new(4., 6.), Point::new(1., 6.)]);
+        assert_eq!(path.shape(), Some(&Shape::Rect(rc)));
+
+        let moved = path.transform(&Transform2D::translation(1., 1.).pre_scale(2., 2.));
+        assert_eq!(moved.shape(), Some(&Shape::Rect(Rect::new(3., 5., 6., 8.))));
+        assert_eq!(path.transform(&Transform2D::rotation(0.5)).shape(), None);
+    ``` */
+    pub fn rect(rc: Rect<T>) -> Self {
+        let mut pb = PathBuilder::new();
+        pb.move_to(Point::new(rc.left, rc.top)).line_to(Point::new(rc.right, rc.top))
+          .line_to(Point::new(rc.right, rc.bottom)).line_to(Point::new(rc.left, rc.bottom))
+          .close();     Self::with_shape(pb, Shape::Rect(rc))
+    }
+
+    /** Rounded rectangle by elliptical radii of corners in order of top-left, top-right,
+        bottom-right and bottom-left, which are scaled down together if the sum of
+        adjacent ones exceeds the side (the same as CSS `border-radius`).
+    ```
+        use ugl_rs::geometry::*;
+        let (rc, r) = (Rect::new(0., 0., 10., 10.), Vector::new(10., 10.));
+        let path = Path::round_rect(rc, [r; 4]);   // scaled down to a circle
+        assert!(path.segments().all(|seg|
+            ((seg.eval(0.3) - Point::new(5., 5.)).length() - 5f32).abs() < 1e-5));
+        assert_eq!(path.shape(), Some(&Shape::RoundRect(rc, [Vector::new(5., 5.); 4])));
+
+        let radii = [Vector::new(1., 2.), Vector::zero(), Vector::new(3., 3.), Vector::zero()];
+        let path = Path::round_rect(Rect::new(0., 0., 20., 10.), radii);
+        assert_eq!(path.verbs().iter().filter(|v| **v == Verb::Conic).count(), 2);
+        assert_eq!(path.tight_bounds(), Some(Rect::new(0., 0., 20., 10.)));
+        assert_eq!(Path::round_rect(rc, [Vector::zero(); 4]), Path::rect(rc));
+    ``` */
+    pub fn round_rect(rc: Rect<T>, radii: [Vector<T>; 4]) -> Self {
+        //  corners with any zero radius are sharp
+        let mut radii = radii.map(|r| { let r = Vector::new(r.x.abs(), r.y.abs());
+            if r.x <= T::ZERO || r.y <= T::ZERO { Vector::zero() } else { r } });
+        if radii.iter().all(|r| *r == Vector::zero()) { return Self::rect(rc) }
+
+        let (w, h) = (rc.width(), rc.height());
+        let ratio = |side: T, sum: T| if T::ZERO < sum { side / sum } else { T::ONE };
+        let [tl, tr, br, bl] = radii;
+        let f = ratio(w, tl.x + tr.x).min(ratio(w, bl.x + br.x))
+            .min(ratio(h, tl.y + bl.y)).min(ratio(h, tr.y + br.y));
+        if f < T::ONE { radii = radii.map(|r| r * f) }
+
+        let [tl, tr, br, bl] = radii;
+        let (l, t, r, b) = (rc.left, rc.top, rc.right, rc.bottom);
+        let w = T::from_f64(core::f64::consts::FRAC_1_SQRT_2);
+        let mut pb = PathBuilder::new();
+        let line_to = |pb: &mut PathBuilder<T>, p: Point<T>|
+            if pb.current_point() != p { pb.line_to(p); };
+
+        pb.move_to(Point::new(l + tl.x, t));    line_to(&mut pb, Point::new(r - tr.x, t));
+        if tr.x != T::ZERO { pb.conic_to(Point::new(r, t), Point::new(r, t + tr.y), w); }
+        line_to(&mut pb, Point::new(r, b - br.y));
+        if br.x != T::ZERO { pb.conic_to(Point::new(r, b), Point::new(r - br.x, b), w); }
+        line_to(&mut pb, Point::new(l + bl.x, b));
+        if bl.x != T::ZERO { pb.conic_to(Point::new(l, b), Point::new(l, b - bl.y), w); }
+        line_to(&mut pb, Point::new(l, t + tl.y));
+        if tl.x != T::ZERO { pb.conic_to(Point::new(l, t), Point::new(l + tl.x, t), w); }
+        pb.close();     Self::with_shape(pb, Shape::RoundRect(rc, radii))
+    }
+
+    /** Ellipse by exact conics, starting from the rightmost point.
+    ```
+        use ugl_rs::geometry::*;
+        let (c, r) = (Point::new(1., 2.), Vector::new(4., 3.));
+        let on_ellipse = |pt: Point| { let v = pt - c;
+            ((v.x / r.x).powi(2) + (v.y / r.y).powi(2) - 1.).abs() };
+
+        let path = Path::ellipse(c, r);
+        assert_eq!(path.shape(), Some(&Shape::Ellipse(c, r)));
+        assert!(path.segments().all(|seg| on_ellipse(seg.eval(0.3)) < 1e-6));
+        assert_eq!(path.tight_bounds(), Some(Rect::from_ltrb(-3., -1., 5., 5.)));
+
+        let path = Path::ellipse_cubic(c, r);
+        assert!(path.segments().all(|seg| on_ellipse(seg.eval(0.3)) < 5e-4));
+        assert_eq!(Path::circle(c, 3.), Path::ellipse(c, Vector::new(3., 3.)));
+    ``` */
+    pub fn ellipse(center: Point<T>, radii: Vector<T>) -> Self {
+        let mut pb = PathBuilder::new();
+        let (rx, ry) = (radii.x, radii.y);  let w = T::from_f64(core::f64::consts::FRAC_1_SQRT_2);
+        let p = |x, y| center + Vector::new(x, y);
+        pb.move_to(p(rx, T::ZERO))
+          .conic_to(p(rx,  ry), p(T::ZERO,  ry), w).conic_to(p(-rx,  ry), p(-rx, T::ZERO), w)
+          .conic_to(p(-rx, -ry), p(T::ZERO, -ry), w).conic_to(p(rx, -ry), p(rx, T::ZERO), w)
+          .close();     Self::with_shape(pb, Shape::Ellipse(center, radii))
+    }
+
+    /// Ellipse approximated by cubics, for the consumers without conic support.
+    pub fn ellipse_cubic(center: Point<T>, radii: Vector<T>) -> Self {
+        let mut pb = PathBuilder::new();
+        let (rx, ry) = (radii.x, radii.y);  let k = T::from_f64(CUBIC_ARC_K);
+        let (kx, ky, o) = (rx * k, ry * k, T::ZERO);
+        let p = |x, y| center + Vector::new(x, y);
+        pb.move_to(p(rx, o))
+          .cubic_to(p( rx,  ky), p( kx,  ry), p(o,  ry))
+          .cubic_to(p(-kx,  ry), p(-rx,  ky), p(-rx, o))
+          .cubic_to(p(-rx, -ky), p(-kx, -ry), p(o, -ry))
+          .cubic_to(p( kx, -ry), p( rx, -ky), p(rx, o))
+          .close();     Self::with_shape(pb, Shape::Ellipse(center, radii))
+    }
+
+    #[inline] pub fn circle(center: Point<T>, radius: T) -> Self {
+        Self::ellipse(center, Vector::new(radius, radius))
+    }
+
+    /** Elliptical arc from the start angle by the sweep angle in radians (clockwise if
+        positive in y-down coordinates, clamped to a full turn), by exact conics.
+    ```
+        use ugl_rs::geometry::*;
+        use core::f32::consts::FRAC_PI_2;
+        let (c, r) = (Point::new(0., 0.), Vector::new(2., 2.));
+        let arc = Path::arc(c, r, 0., FRAC_PI_2, ArcKind::Open);
+        assert_eq!(arc.verbs(), &[Verb::Move, Verb::Conic]);
+        assert!((arc.points()[2] - Point::new(0., 2.)).length() < 1e-6);
+
+        let chord = Path::arc(c, r, 0., -FRAC_PI_2 * 3., ArcKind::Chord);
+        assert_eq!(chord.verbs(),
+            &[Verb::Move, Verb::Conic, Verb::Conic, Verb::Conic, Verb::Close]);
+        let pie = Path::arc(c, r, FRAC_PI_2, FRAC_PI_2, ArcKind::Pie);
+        assert_eq!(pie.verbs(), &[Verb::Move, Verb::Line, Verb::Conic, Verb::Close]);
+        let rc = pie.tight_bounds().unwrap();
+        assert!((rc.left + 2.).abs() < 1e-6 && rc.right == 0. && (rc.bottom - 2.).abs() < 1e-6);
+    ``` */
+    pub fn arc(center: Point<T>, radii: Vector<T>, start: T, sweep: T, kind: ArcKind) -> Self {
+        let tau = T::from_f64(TAU);     let sweep = sweep.clamp(-tau, tau);
+        let n = (sweep.abs() / T::from_f64(FRAC_PI_2) - T::from_f32(1e-3)).ceil().max(T::ONE);
+        let (step, count) = (sweep / n, n.to_i32());
+        let (_, w) = (step * T::HALF).sin_cos();
+        let p = |angle: T, s: T| {
+            let (sin, cos) = angle.sin_cos();
+            center + Vector::new(radii.x * cos / s, radii.y * sin / s)
+        };
+
+        let mut pb = PathBuilder::new();
+        if kind == ArcKind::Pie { pb.move_to(center).line_to(p(start, T::ONE)); }
+        else { pb.move_to(p(start, T::ONE)); }
+        for i in 1..=count {
+            let a = start + step * T::from_i32(i);
+            pb.conic_to(p(a - step * T::HALF, w), p(a, T::ONE), w);
+        }
+        if kind != ArcKind::Open { pb.close(); }    pb.build()
+    }
+
+    /** Open or closed polyline through the points, e.g. triangle or arbitrary polygon.
+    ```
+        use ugl_rs::geometry::*;
+        let pts = [Point::new(0., 0.), Point::new(4., 0.), Point::new(0., 3.)];
+        let tri = Path::polyline(&pts, true);
+        assert_eq!(tri.verbs(), &[Verb::Move, Verb::Line, Verb::Line, Verb::Close]);
+        assert_eq!(tri.segments().map(|seg| seg.start().distance(seg.end())).sum::<f32>(), 12.);
+    ``` */
+    pub fn polyline(pts: &[Point<T>], close: bool) -> Self {
+        let mut pb = PathBuilder::new();
+        let Some((&first, rest)) = pts.split_first() else { return pb.build() };
+        pb.move_to(first);  rest.iter().for_each(|&pt| { pb.line_to(pt); });
+        if close { pb.close(); }    pb.build()
+    }
+
+    /** Regular polygon inscribed in the circle, with the first vertex at the top
+        rotated by the angle in radians (clockwise in y-down coordinates),
+        empty if less than 3 sides.
+    ```
+        use ugl_rs::geometry::*;
+        let hexagon = Path::polygon(Point::new(0., 0.), 2., 6, 0.);
+        assert_eq!(hexagon.points().len(), 6);
+        assert!((hexagon.points()[0] - Point::new(0., -2.)).length() < 1e-6);
+        assert!(hexagon.segments().all(|seg|
+            (seg.start().distance(seg.end()) - 2f32).abs() < 1e-5));
+
+        let star = Path::star(Point::new(0., 0.), 2., 1., 5, 0.);
+        assert_eq!(star.points().len(), 10);
+        assert!(star.points().iter().enumerate().all(|(i, pt)|
+            (pt.length() - if i % 2 == 0 { 2f32 } else { 1. }).abs() < 1e-6));
+        assert!(Path::polygon(Point::new(0., 0.), 2., 0, 0.).is_empty());
+        assert!(Path::star(Point::new(0., 0.), 2., 1., 0, 0.).is_empty());
+    ``` */
+    pub fn polygon(center: Point<T>, radius: T, sides: u32, rotation: T) -> Self {
+        Self::regular(center, sides as usize, rotation, |_| radius)
+    }
+
+    /// Star with the points on the outer circle and the concave vertices on the inner circle,
+    /// with the first point at the top rotated by the angle in radians, empty if less than 2.
+    pub fn star(center: Point<T>, outer: T, inner: T, points: u32, rotation: T) -> Self {
+        Self::regular(center, points as usize * 2, rotation,
+            |i| if i % 2 == 0 { outer } else { inner })
+    }
+
+    fn regular(center: Point<T>, n: usize, rotation: T, radius: impl Fn(usize) -> T) -> Self {
+        if n < 3 { return Self::default() }
+        let step = T::from_f64(TAU) / T::from_i32(n as i32);
+        let pts: Vec<_> = (0..n).map(|i| {
+            let angle = rotation - T::from_f64(FRAC_PI_2) + step * T::from_i32(i as i32);
+            let (sin, cos) = angle.sin_cos();   let r = radius(i);
+            center + Vector::new(r * cos, r * sin)
+        }).collect();   Self::polyline(&pts, true)
+    }
+}
@@ -0,0 +1,109 @@
+
+//  https://en.wikipedia.org/wiki/Ramer%E2%80%93Douglas%E2%80%93Peucker_algorithm
+//  Simplification keeps the structure of contours within the tolerance: curves flat within
+//  it become lines, runs of lines are reduced by Ramer–Douglas–Peucker, and consecutive
+//  curves joined smoothly are merged into a cubic fitted to their samples (see `fit`)
+//  if it's within the tolerance, e.g. the pieces split by boolean operations or offsetting.
+
+use crate::{geometry::{fit::fit_single, Path, PathBuilder, Point, Segment}, math::Scalar};
+
+/// Samples of each curve for fitting of the merged curves.
+const SAMPLES: i32 = 16;
+
+/// Cosine of the maximum angle (about 1 degree) between the tangents of curves to merge.
+const SMOOTH_COS: f64 = 0.9998;
+
+impl<T: Scalar> Path<T> {
+    /** Simplify within the tolerance, by dropping zero-length segments, converting flat
+        curves into lines, reducing runs of lines, and merging smoothly joined curves.
+    ```
+        use ugl_rs::geometry::*;
+        let p = |x, y| Point::new(x, y);
+        let mut pb = PathBuilder::new();    // noisy polyline along a line, then a corner
+        pb.move_to(p(0., 0.));
+        (1..=10).for_each(|i| { pb.line_to(p(i as f32, if i % 2 == 0 { 0.01 } else { -0.01 })); });
+        pb.line_to(p(10., 10.)).quad_to(p(15., 10.05), p(20., 10.));    // flat quad
+        assert_eq!(pb.build().simplify(0.1).points(),
+            [p(0., 0.), p(10., 0.01), p(10., 10.), p(20., 10.)]);
+
+        let cubic = Segment::Cubic([p(0., 0.), p(0., 10.), p(10., 10.), p(10., 0.)]);
+        let mut pb = PathBuilder::new();    pb.move_to(cubic.start());
+        for (t0, t1) in [(0., 0.3), (0.3, 0.7), (0.7, 1.)] {
+            if let Segment::Cubic([_, c1, c2, p]) = cubic.sub(t0, t1) { pb.cubic_to(c1, c2, p); }
+        }
+        let merged = pb.build().simplify(0.01);
+        assert_eq!(merged.verbs(), [Verb::Move, Verb::Cubic]);
+        assert!((0..=10).all(|k| merged.distance(cubic.eval(k as f32 / 10.)).unwrap() <= 0.01));
+    ``` */
+    pub fn simplify(&self, tolerance: T) -> Self {
+        let cv = |p: Point<T>| Point::new(p.x.to_f64(), p.y.to_f64());
+        let dir = |seg: &Segment<T>, t: T| cv(seg.tangent(t)).normalize();
+        let is_line = |seg: &Segment<T>| matches!(seg, Segment::Line(_));
+        let flat = |seg: &Segment<T>| {
+            let chord = Segment::Line([seg.start(), seg.end()]);
+            match *seg {
+                Segment::Line(_) => true,
+                Segment::Quad([_, c, _]) | Segment::Conic([_, c, _], _) =>
+                    chord.nearest(c).1 <= tolerance,
+                Segment::Cubic([_, c1, c2, _]) =>
+                    chord.nearest(c1).1 <= tolerance && chord.nearest(c2).1 <= tolerance,
+            }
+        };
+
+        let mut pb = PathBuilder::new();    pb.fill_rule(self.fill_rule);
+        for (mut segs, closed) in self.contours() {
+            segs.retain(|seg| seg.tangent(T::ZERO) != Point::zero());
+            for seg in segs.iter_mut().filter(|seg| flat(seg)) {
+                *seg = Segment::Line([seg.start(), seg.end()]);
+            }
+            let n = segs.len();     if n == 0 { continue }
+            if closed {     // started at a boundary of the runs of lines
+                let r = (0..n).find(|&i| is_line(&segs[(i + n - 1) % n]) != is_line(&segs[i]));
+                segs.rotate_left(r.unwrap_or(0));
+            }
+
+            pb.move_to(segs[0].start());
+            let mut i = 0;
+            while i < n {
+                let mut j = i + 1;
+                if is_line(&segs[i]) {
+                    while j < n && is_line(&segs[j]) { j += 1 }
+                    let pts: Vec<_> = core::iter::once(segs[i].start())
+                        .chain(segs[i..j].iter().map(|seg| seg.end())).collect();
+                    let keep = rdp(&pts, tolerance);
+                    let last = if closed && j == n { pts.len() - 1 } else { pts.len() };
+                    (1..last).filter(|&k| keep[k]).for_each(|k| { pb.line_to(pts[k]); });
+                    i = j;  continue    // the closing line by `close`
+                }
+
+                let mut merged = segs[i];
+                while j < n && !is_line(&segs[j]) && dir(&segs[j - 1], T::ONE)
+                    .zip(dir(&segs[j], T::ZERO)).is_some_and(|(a, b)| SMOOTH_COS <= a.dot(b)) {
+                    let pts: Vec<_> = segs[i..=j].iter().flat_map(|seg| (0..SAMPLES)
+                        .map(|k| cv(seg.eval(T::from_i32(k) / T::from_i32(SAMPLES)))))
+                        .chain(core::iter::once(cv(segs[j].end()))).collect();
+                    let (t0, t1) =
+                        (dir(&segs[i], T::ZERO).unwrap(), -dir(&segs[j], T::ONE).unwrap());
+                    let Ok([_, c1, c2, _]) = fit_single(&pts, t0, t1, tolerance.to_f64())
+                        else { break };
+                    let cv = |p: Point<f64>| Point::new(T::from_f64(p.x), T::from_f64(p.y));
+                    merged = Segment::Cubic([segs[i].start(), cv(c1), cv(c2), segs[j].end()]);
+                    j += 1;
+                }   pb.push_segment(merged);    i = j;
+            }   if closed { pb.close(); }
+        }   pb.build()
+    }
+}
+
+/// Points to keep by Ramer–Douglas–Peucker, the first and the last are always kept.
+fn rdp<T: Scalar>(pts: &[Point<T>], tolerance: T) -> Vec<bool> {
+    let mut keep = vec![false; pts.len()];
+    keep[0] = true;     keep[pts.len() - 1] = true;
+    let mut stack = vec![(0, pts.len() - 1)];
+    while let Some((a, b)) = stack.pop() {
+        let chord = Segment::Line([pts[a], pts[b]]);
+        let Some((k, d)) = (a + 1..b).map(|k| (k, chord.nearest(pts[k]).1))
+            .max_by(|x, y| x.1.partial_cmp(&y.1).unwrap()) else { continue };
+        if tolerance < d { keep[k] = true;  stack.push((a, k));     stack.push((k, b)); }
+    }   keep
+}
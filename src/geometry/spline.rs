@@ -0,0 +1,217 @@
+
+//  https://en.wikipedia.org/wiki/Centripetal_Catmull%E2%80%93Rom_spline
+//  https://pages.mtu.edu/~shene/COURSES/cs3621/NOTES/spline/NURBS/NURBS-knot-insert.html
+//  Les Piegl and Wayne Tiller, The NURBS Book, 2nd ed., 1997.
+//  Catmull-Rom splines are exact cubics by the tangents of the non-uniform parameterization.
+//  B-splines are split into Bézier spans by inserting knots (Boehm's algorithm) until each
+//  distinct knot of the domain has the multiplicity of the degree, in homogeneous coordinates,
+//  the spans of degree at most 2 (rational or not) and of degree 3 (not rational) are exact
+//  lines, quads, conics and cubics, others are approximated by fitting cubics (see `fit`).
+
+use crate::{geometry::{fit::fit_cubics, flatten::length, Path, PathBuilder, Point}, math::Scalar};
+
+/// Homogeneous point of rational curves, `(x w, y w, w)`.
+type H = [f64; 3];
+
+/// Samples of each Bézier span to fit cubics approximating it.
+const SAMPLES: usize = 32;
+
+/// Parameterizations of Catmull-Rom splines, by the power of the distances of the points
+/// as the knot intervals.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)] pub enum CatmullRom {
+    /** power 0 */ Uniform,
+    /** power 0.5, without cusps and self-intersections within segments */ #[default] Centripetal,
+    /** power 1 */ Chordal,
+}
+
+impl<T: Scalar> Path<T> {
+    /** Catmull-Rom spline interpolating the points by cubics, with the end tangents of
+        open spline by the reflected neighbors. Consecutive coincident points are merged.
+    ```
+        use ugl_rs::geometry::*;
+        let p = |x, y| Point::new(x, y);
+        let pts = [p(0., 0.), p(1., 10.), p(2., 10.), p(10., 0.)];
+        for kind in [CatmullRom::Uniform, CatmullRom::Centripetal, CatmullRom::Chordal] {
+            let spline = Path::catmull_rom(&pts, kind, false);
+            assert_eq!(spline.verbs(), [Verb::Move, Verb::Cubic, Verb::Cubic, Verb::Cubic]);
+            assert!(spline.segments().zip(&pts[1..]).all(|(seg, &pt)| seg.end() == pt));
+        }
+
+        let uniform = Path::catmull_rom(&pts, CatmullRom::Uniform, false);
+        assert_eq!(uniform.points()[4], p(1., 10.) + (p(2., 10.) - p(0., 0.)) / 6.);
+        let closed = Path::catmull_rom(&pts, CatmullRom::Centripetal, true);
+        assert_eq!(closed.verbs().len(), 6);    // with the cubic back to the first point
+        assert_eq!(closed.segments().last().unwrap().end(), p(0., 0.));
+
+        #[cfg(feature = "fixed")] {     use ugl_rs::math::{I16F16, Scalar};
+            let f = |x: i32, y: i32| Point::new(I16F16::from_i32(x), I16F16::from_i32(y));
+            let path = Path::catmull_rom(&[f(0, 0), f(300, 0), f(300, 300)],
+                CatmullRom::Chordal, false);    // no overflow of squared distances
+            assert_eq!(path.points().last(), Some(&f(300, 300)));
+        }
+    ``` */
+    pub fn catmull_rom(pts: &[Point<T>], kind: CatmullRom, close: bool) -> Self {
+        let mut pts = pts.to_vec();     pts.dedup();
+        if close && 2 < pts.len() && pts[0] == pts[pts.len() - 1] { pts.pop(); }
+        let mut pb = PathBuilder::new();
+        let n = pts.len() as isize;     if n < 2 { return Self::polyline(&pts, false) }
+
+        let at = |i: isize| if close { pts[i.rem_euclid(n) as usize] }
+            else if i < 0 { pts[0] * T::TWO - pts[1] }
+            else if n <= i { pts[n as usize - 1] * T::TWO - pts[n as usize - 2] }
+            else { pts[i as usize] };
+        let dt = |a: Point<T>, b: Point<T>| match kind {
+            CatmullRom::Uniform => T::ONE,
+            CatmullRom::Centripetal => length(b - a).sqrt(),
+            CatmullRom::Chordal => length(b - a),
+        };
+
+        pb.move_to(pts[0]);
+        let third = T::ONE / T::from_i32(3);
+        for i in 0..if close { n } else { n - 1 } {
+            let [p0, p1, p2, p3] = [i - 1, i, i + 1, i + 2].map(at);
+            let (d0, d1, d2) = (dt(p0, p1), dt(p1, p2), dt(p2, p3));
+            //  tangents at the inner points by the derivatives of Barry and Goldman's pyramid
+            let m1 = (p1 - p0) / d0 - (p2 - p0) / (d0 + d1) + (p2 - p1) / d1;
+            let m2 = (p2 - p1) / d1 - (p3 - p1) / (d1 + d2) + (p3 - p2) / d2;
+            pb.cubic_to(p1 + m1 * (d1 * third), p2 - m2 * (d1 * third), p2);
+        }   if close { pb.close(); }    pb.build()
+    }
+
+    /** Uniform B-spline of the degree (clamped to 1..=3) by the control points,
+        which doesn't pass through the first and the last points if it's open,
+        use `nurbs` with clamped knots for that.
+    ```
+        use ugl_rs::geometry::*;
+        let p = |x, y| Point::new(x, y);
+        let pts = [p(0., 0.), p(0., 6.), p(6., 6.), p(6., 0.)];
+        let open = Path::bspline(&pts, 3, false);
+        assert_eq!(open.verbs(), [Verb::Move, Verb::Cubic]);
+        assert_eq!(open.points(), [p(1., 5.), p(2., 6.), p(4., 6.), p(5., 5.)]);
+
+        let closed = Path::bspline(&pts, 2, true);  // quads through the midpoints
+        assert_eq!(closed.verbs(), [Verb::Move, Verb::Quad, Verb::Quad, Verb::Quad, Verb::Quad,
+            Verb::Close]);
+        assert_eq!(closed.points()[..3], [p(0., 3.), p(0., 6.), p(3., 6.)]);
+    ``` */
+    pub fn bspline(pts: &[Point<T>], degree: u32, close: bool) -> Self {
+        let p = degree.clamp(1, 3) as usize;
+        let mut cps: Vec<_> = pts.iter().map(|pt| [pt.x.to_f64(), pt.y.to_f64(), 1.]).collect();
+        if close && 2 <= cps.len() { (0..p).for_each(|i| cps.push(cps[i])) }  // periodic
+        if cps.len() <= p { return Self::default() }
+
+        let knots: Vec<_> = (0..=cps.len() + p).map(|i| i as f64).collect();
+        let mut pb = PathBuilder::new();
+        bezier_spans(cps, knots, p).iter().for_each(|span| push_span(&mut pb, span, 0.));
+        if close { pb.close(); }    pb.build()
+    }
+
+    /** NURBS curve of the degree by the control points, weights (all 1 if it's empty,
+        for non-rational B-spline) and knots (of the count of points + degree + 1),
+        with the spans that aren't lines, quads, conics or non-rational cubics approximated
+        by cubics within the tolerance. `None` for invalid weights or knots.
+    ```
+        use ugl_rs::geometry::*;
+        let p = |x, y| Point::new(x, y);
+        let w = core::f32::consts::FRAC_1_SQRT_2;   // circle by 4 conics
+        let pts = [p(1., 0.), p(1., 1.), p(0., 1.), p(-1., 1.), p(-1., 0.),
+            p(-1., -1.), p(0., -1.), p(1., -1.), p(1., 0.)];
+        let weights = [1., w, 1., w, 1., w, 1., w, 1.];
+        let knots = [0., 0., 0., 1., 1., 2., 2., 3., 3., 4., 4., 4.];
+        let circle = Path::nurbs(&pts, &weights, &knots, 2, 0.01).unwrap();
+        assert_eq!(circle.verbs()[1..], [Verb::Conic; 4]);
+        assert!(circle.segments().all(|seg| (0..=10).all(|k|
+            (seg.eval(k as f32 / 10.).length() - 1.).abs() < 1e-6)));
+
+        let pts = [p(0., 0.), p(0., 6.), p(6., 6.), p(6., 0.)];    // uniform knots
+        let knots = [0., 1., 2., 3., 4., 5., 6., 7.];
+        assert_eq!(Path::nurbs(&pts, &[], &knots, 3, 0.01), Some(Path::bspline(&pts, 3, false)));
+        let knots = [0., 0., 0., 0., 1., 1., 1., 1.];     // clamped knots, a Bézier curve
+        assert_eq!(Path::nurbs(&pts, &[], &knots, 3, 0.01).unwrap().points(), pts);
+        assert_eq!(Path::nurbs(&pts, &[], &knots[1..], 3, 0.01), None);
+
+        let k = 2. * w / (1. + 2. * w);     // rational cubic of the quarter circle
+        let pts = [p(1., 0.), p(1., k), p(k, 1.), p(0., 1.)];
+        let weights = [1., (1. + 2. * w) / 3., (1. + 2. * w) / 3., 1.];
+        let arc = Path::nurbs(&pts, &weights, &knots, 3, 0.001).unwrap();
+        assert!(arc.segments().all(|seg| (0..=10).all(|k|
+            (seg.eval(k as f32 / 10.).length() - 1.).abs() < 0.001)));
+    ``` */
+    pub fn nurbs(pts: &[Point<T>], weights: &[T], knots: &[T], degree: u32, tolerance: T)
+        -> Option<Self> {
+        let (n, p) = (pts.len(), degree as usize);
+        if p == 0 || n <= p || !(weights.is_empty() || weights.len() == n) ||
+            knots.len() != n + p + 1 || knots.windows(2).any(|w| w[1] < w[0]) ||
+            weights.iter().any(|&w| w <= T::ZERO) || knots[n] <= knots[p] { return None }
+
+        let cps = pts.iter().enumerate().map(|(i, pt)| {
+            let w = weights.get(i).map_or(1., |w| w.to_f64());
+            [pt.x.to_f64() * w, pt.y.to_f64() * w, w]
+        }).collect();
+        let knots = knots.iter().map(|u| u.to_f64()).collect();
+
+        let mut pb = PathBuilder::new();
+        bezier_spans(cps, knots, p).iter()
+            .for_each(|span| push_span(&mut pb, span, tolerance.to_f64()));
+        Some(pb.build())
+    }
+}
+
+/// Bézier spans of the B-spline in homogeneous coordinates, by inserting knots until
+/// each distinct knot of the domain `[knots[p], knots[n]]` has the multiplicity `p`.
+fn bezier_spans(mut cps: Vec<H>, mut knots: Vec<f64>, p: usize) -> Vec<Vec<H>> {
+    let mut values = knots[p..=cps.len()].to_vec();     values.dedup();
+    for u in values {
+        let s = knots.iter().filter(|&&k| k == u).count();
+        for _ in s..p { insert_knot(&mut cps, &mut knots, p, u) }
+    }   //  the span of `[knots[k], knots[k + 1])` is controlled by `cps[k - p..=k]`
+    (p..cps.len()).filter(|&k| knots[k] < knots[k + 1]).map(|k| cps[k - p..=k].to_vec()).collect()
+}
+
+/// Insert the knot (in the domain) once by Boehm's algorithm.
+fn insert_knot(cps: &mut Vec<H>, knots: &mut Vec<f64>, p: usize, u: f64) {
+    let k = knots.partition_point(|&x| x <= u) - 1;     // knots[k] <= u < knots[k + 1]
+    let s = knots.iter().filter(|&&x| x == u).count();
+    let q = (0..=cps.len()).map(|i| if i + p <= k { cps[i] } else if k < i + s { cps[i - 1] }
+        else {
+            let a = (u - knots[i]) / (knots[i + p] - knots[i]);
+            [0, 1, 2].map(|j| cps[i - 1][j] + (cps[i][j] - cps[i - 1][j]) * a)
+        }).collect();
+    *cps = q;   knots.insert(k + 1, u);
+}
+
+/// Append the Bézier span in homogeneous coordinates, exactly if it's representable,
+/// or approximated by cubics within the tolerance.
+fn push_span<T: Scalar>(pb: &mut PathBuilder<T>, span: &[H], tolerance: f64) {
+    let cv = |p: Point<f64>| Point::new(T::from_f64(p.x), T::from_f64(p.y));
+    let pts: Vec<_> = span.iter().map(|h| Point::new(h[0] / h[2], h[1] / h[2])).collect();
+    let (w0, p) = (span[0][2], span.len() - 1);
+    if pb.current_point() != cv(pts[0]) { pb.move_to(cv(pts[0])); }
+
+    let rational = span.iter().any(|h| f64::EPSILON * 16. < (h[2] / w0 - 1.).abs());
+    match (p, rational) {
+        (1, _) => { pb.line_to(cv(pts[1])); }
+        (2, false) => { pb.quad_to(cv(pts[1]), cv(pts[2])); }
+        (2, true) => {
+            let w = span[1][2] / (span[0][2] * span[2][2]).sqrt();
+            pb.conic_to(cv(pts[1]), cv(pts[2]), T::from_f64(w));
+        }
+        (3, false) => { pb.cubic_to(cv(pts[1]), cv(pts[2]), cv(pts[3])); }
+        _ => {
+            let samples: Vec<_> = (0..=SAMPLES).map(|i| {   // by de Casteljau's algorithm
+                let (t, mut h) = (i as f64 / SAMPLES as f64, span.to_vec());
+                for m in (1..=p).rev() { for j in 0..m {
+                    h[j] = [0, 1, 2].map(|c| h[j][c] + (h[j + 1][c] - h[j][c]) * t);
+                } } Point::new(h[0][0] / h[0][2], h[0][1] / h[0][2])
+            }).collect();
+            //  end tangents by the first distinct control points from the ends
+            let t0 = pts[1..].iter().find_map(|&b| (b - pts[0]).normalize());
+            let t1 = pts[..p].iter().rev().find_map(|&b| (b - pts[p]).normalize());
+            let (Some(t0), Some(t1)) = (t0, t1) else { pb.line_to(cv(pts[p]));   return };
+
+            let mut cubics = Vec::new();
+            fit_cubics(&samples, t0, t1, tolerance, &mut cubics);
+            cubics.into_iter().for_each(|[c1, c2, p]| { pb.cubic_to(cv(c1), cv(c2), cv(p)); });
+        }
+    }
+}
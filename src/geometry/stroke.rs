@@ -0,0 +1,269 @@
+
+//  https://www.w3.org/TR/svg-strokes/
+//  https://github.com/google/skia/blob/main/src/core/SkStroke.cpp
+//  Curves are flattened first, then the polylines are offset to both sides,
+//  resulting an outline to be filled by non-zero rule.
+
+use crate::{geometry::{flatten::length, FillRule, Path, PathBuilder, PathCmd, Point, Segment,
+    Vector},
+    math::{Scalar, Transform2D}};
+
+/// Shapes at the ends of open sub-paths, and of zero-length ones even if closed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)] pub enum LineCap {
+    #[default] Butt, Round, /** extended by half of the width */ Square,
+}
+
+/// Shapes at the corners of sub-paths.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)] pub enum LineJoin {
+    /** fallback to `Bevel` if exceeding the miter limit */ #[default] Miter,
+    /** clipped at the miter limit instead of fallback to `Bevel` */ MiterClip,
+    Round, Bevel,
+}
+
+/** Stroke style, the defaults are the same as SVG.
+```
+    use ugl_rs::{geometry::*, math::Transform2D};
+    let line = |pts: &[(f32, f32)]| { let mut pb = PathBuilder::new();
+        pb.move_to(pts[0].into());  pts[1..].iter().for_each(|&pt| { pb.line_to(pt.into()); });
+        pb.build()
+    };
+    let mut style = Stroke { width: 2., ..Default::default() };
+    let outline = |path: &Path, style: &Stroke| path.stroke(style, 0.1).tight_bounds().unwrap();
+
+    let path = line(&[(0., 0.), (10., 0.)]);
+    assert_eq!(outline(&path, &style), Rect::from_ltrb(0., -1., 10., 1.));
+    style.cap = LineCap::Square;
+    assert_eq!(outline(&path, &style), Rect::from_ltrb(-1., -1., 11., 1.));
+    style.cap = LineCap::Round;     let rc = outline(&path, &style);
+    assert!((rc.left + 1.).abs() < 1e-5 && (rc.right - 11.).abs() < 1e-5);
+
+    let path = line(&[(0., 0.), (10., 0.), (10., 10.)]);    // right-angle corner
+    assert!(path.stroke(&style, 0.1).points().contains(&Point::new(11., -1.)));
+    style.join = LineJoin::Bevel;
+    assert!(!path.stroke(&style, 0.1).points().contains(&Point::new(11., -1.)));
+
+    let path = line(&[(0., 0.), (10., 0.), (0., 1.)]);      // sharp corner
+    style.join = LineJoin::Miter;   assert!(outline(&path, &style).right < 11.);
+    style.join = LineJoin::MiterClip;
+    assert!((outline(&path, &style).right - 14.).abs() < 0.1);     // 10 + 4 * 1
+    let path = line(&[(0., 0.), (10., 0.), (0., 0.)]);      // cusp
+    style.join = LineJoin::Round;   assert!((outline(&path, &style).right - 11.).abs() < 1e-5);
+    #[cfg(feature = "fixed")] {     use ugl_rs::math::I16F16;   // no miter at the cusp
+        let p = |x: i32| Point::new(I16F16::from_num(x), I16F16::from_num(0));
+        let mut pb = PathBuilder::new();    pb.move_to(p(0)).line_to(p(10)).line_to(p(0));
+        let style = Stroke { join: LineJoin::Bevel, ..Default::default() };
+        assert!(!pb.build().stroke(&style, I16F16::from_num(0.1)).points().is_empty());
+        let mut pb = PathBuilder::new();    // too short to have a direction
+        pb.move_to(p(0)).line_to(Point::new(I16F16::DELTA, I16F16::ZERO)).line_to(p(10));
+        assert!(!pb.build().stroke(&style, I16F16::from_num(0.1)).is_empty());
+        let mut pb = PathBuilder::new();
+        pb.move_to(p(0)).line_to(Point::new(I16F16::DELTA * 3, I16F16::DELTA * 2));
+        let style = Stroke { cap: LineCap::Round, ..style };
+        assert!(!pb.build().stroke(&style, I16F16::from_num(0.1)).is_empty());
+    }
+
+    let mut pb = PathBuilder::new();    // closed contour results in 2 loops
+    pb.move_to(Point::new(0., 0.)).line_to(Point::new(10., 0.))
+      .line_to(Point::new(10., 10.)).close();
+    let stroked = pb.build().stroke(&Stroke { width: 2., ..Default::default() }, 0.1);
+    assert_eq!(stroked.verbs().iter().filter(|v| **v == Verb::Close).count(), 2);
+
+    let dot = line(&[(5., 5.), (5., 5.)]);      // zero-length with round cap
+    style.cap = LineCap::Round;     let rc = outline(&dot, &style);
+    assert!((rc.width() - 2.).abs() < 1e-5 && (rc.center() - Point::new(5., 5.)).length() < 1e-5);
+    let dot: Path = dot.iter().chain([PathCmd::Close]).collect();    // even if closed
+    assert_eq!(outline(&dot, &style), rc);
+
+    assert!(Stroke { width: 0.5, ..Default::default() }
+        .is_hairline(&Transform2D::scaling(2., 1.)));
+    assert!(!Stroke { width: 1.5, ..Default::default() }.is_hairline(&Transform2D::rotation(1.)));
+``` */
+#[derive(Clone, Copy, Debug, PartialEq)] pub struct Stroke<T: Scalar = f32> {
+    pub width: T, pub cap: LineCap, pub join: LineJoin,
+    /// limit of the ratio of the miter length to the width
+    pub miter_limit: T,
+}
+
+impl<T: Scalar> Default for Stroke<T> {
+    fn default() -> Self { Self { width: T::ONE, cap: LineCap::Butt, join: LineJoin::Miter,
+        miter_limit: T::from_i32(4) } }
+}
+
+impl<T: Scalar> Stroke<T> {
+    /// Whether the width is not wider than 1 pixel after the transformation,
+    /// to be drawn as hairline (e.g. by anti-aliased lines) for the fast path.
+    pub fn is_hairline(&self, ts: &Transform2D<T>) -> bool {
+        //  the largest singular value of the linear part
+        let (a, b, c, d) = (ts.a, ts.b, ts.c, ts.d);
+        let e = (a * a + b * b + c * c + d * d) * T::HALF;
+        let h = (a * a + b * b - c * c - d * d) * T::HALF;  let k = a * c + b * d;
+        self.width * (e + (h * h + k * k).sqrt()).sqrt() <= T::ONE
+    }
+
+    /** Interpolation to the other at `t`, of the width and the miter limit,
+        with the cap and the join of the nearer end.
+    ```
+        use ugl_rs::geometry::*;
+        let a = Stroke { width: 1., ..Default::default() };
+        let b = Stroke { width: 3., join: LineJoin::Round, miter_limit: 2., ..a };
+        assert_eq!(a.lerp(&b, 0.25), Stroke { width: 1.5, miter_limit: 3.5, ..a });
+        assert_eq!(a.lerp(&b, 0.75).join, LineJoin::Round);
+    ``` */
+    pub fn lerp(&self, other: &Self, t: T) -> Self {
+        let near = if t < T::HALF { self } else { other };
+        Self { width: self.width.lerp(other.width, t), cap: near.cap, join: near.join,
+            miter_limit: self.miter_limit.lerp(other.miter_limit, t) }
+    }
+}
+
+/// Polyline of a sub-path, `smooth` marks the interior vertices of flattened curves.
+struct Contour<T: Scalar> { pts: Vec<Point<T>>, smooth: Vec<bool>, closed: bool }
+
+impl<T: Scalar> Contour<T> {
+    fn new(p: Point<T>) -> Self { Self { pts: vec![p], smooth: vec![false], closed: false } }
+
+    /// Remove degenerate segments, too short to have a direction.
+    fn dedup(&mut self) {
+        let degenerate = |a: Point<T>, b: Point<T>| (b - a).normalize().is_none();
+        let (mut pts, mut smooth) = (Vec::with_capacity(self.pts.len()), Vec::new());
+        for (&pt, &sm) in self.pts.iter().zip(&self.smooth) {
+            if pts.last().is_some_and(|&last| degenerate(last, pt)) {
+                let last = smooth.len() - 1;    smooth[last] &= sm;
+            } else { pts.push(pt);  smooth.push(sm); }
+        }
+        if self.closed { while 1 < pts.len() && degenerate(pts[pts.len() - 1], pts[0]) {
+            pts.pop();  smooth.pop();
+        } }     (self.pts, self.smooth) = (pts, smooth);
+    }
+
+    fn reversed(&self) -> Self {
+        Self { pts: self.pts.iter().rev().copied().collect(),
+            smooth: self.smooth.iter().rev().copied().collect(), closed: self.closed }
+    }
+}
+
+impl<T: Scalar> Path<T> {
+    /// Convert into the outline of stroking, to be filled by non-zero rule,
+    /// with curves flattened within the tolerance. It's empty for zero width.
+    pub fn stroke(&self, style: &Stroke<T>, tolerance: T) -> Self {
+        let mut pb = PathBuilder::new();    pb.fill_rule(FillRule::NonZero);
+        let hw = style.width.abs() * T::HALF;
+        if hw <= T::ZERO { return pb.build() }
+
+        let (mut contours, mut cur, mut last) = (Vec::new(), None, Point::zero());
+        for cmd in self.iter() {
+            let seg = match cmd {
+                PathCmd::MoveTo(p) => {
+                    contours.extend(cur.replace(Contour::new(p)));  last = p;   continue
+                }
+                PathCmd::Close => { if let Some(mut c) = cur.take() {
+                    c.closed = true;    last = c.pts[0];    contours.push(c);
+                }   continue }
+                PathCmd::LineTo(p) => Segment::Line([last, p]),
+                PathCmd::QuadTo(c, p) => Segment::Quad([last, c, p]),
+                PathCmd::CubicTo(c1, c2, p) => Segment::Cubic([last, c1, c2, p]),
+                PathCmd::ConicTo(c, p, w) => Segment::Conic([last, c, p], w),
+            };
+
+            let c = cur.get_or_insert_with(|| Contour::new(last));
+            seg.flatten(tolerance, |pt| { c.pts.push(pt);  c.smooth.push(true); });
+            *c.smooth.last_mut().unwrap() = false;  last = seg.end();
+        }   contours.extend(cur);
+
+        for mut c in contours { c.dedup();  style.stroke_contour(&c, hw, tolerance, &mut pb) }
+        pb.build()
+    }
+}
+
+impl<T: Scalar> Stroke<T> {
+    fn stroke_contour(&self, c: &Contour<T>, hw: T, tol: T, pb: &mut PathBuilder<T>) {
+        let radii = Vector::new(hw, hw);
+        if c.pts.len() == 1 {   // zero-length sub-path is drawn by caps only
+            let (p, dx, dy) = (c.pts[0], Vector::new(hw, T::ZERO), Vector::new(T::ZERO, hw));
+            match self.cap {
+                LineCap::Butt => (),
+                LineCap::Round => { pb.move_to(p + dx).arc_to(radii, T::ZERO, false, false, p - dx)
+                    .arc_to(radii, T::ZERO, false, false, p + dx).close(); }
+                LineCap::Square => { pb.move_to(p - dx - dy).line_to(p + dx - dy)
+                    .line_to(p + dx + dy).line_to(p - dx + dy).close(); }
+            }   return
+        }
+
+        let rev = c.reversed();
+        if c.closed {
+            self.side(c, hw, tol, pb);  pb.close();
+            self.side(&rev, hw, tol, pb);   pb.close();
+        } else {
+            let cap = |c: &Contour<T>, pb: &mut PathBuilder<T>| {
+                let (p, q) = (c.pts[c.pts.len() - 1], c.pts[c.pts.len() - 2]);
+                let u = (p - q).normalize().unwrap_or_default();    let n = u.perp() * hw;
+                match self.cap {
+                    LineCap::Butt => { pb.line_to(p - n); }
+                    LineCap::Round => { pb.arc_to(radii, T::ZERO, false, false, p - n); }
+                    LineCap::Square => { pb.line_to(p + n + u * hw).line_to(p - n + u * hw)
+                        .line_to(p - n); }
+                }
+            };
+            self.side(c, hw, tol, pb);      cap(c, pb);
+            self.side(&rev, hw, tol, pb);   cap(&rev, pb);  pb.close();
+        }
+    }
+
+    /// Offset to the left side (rotated by +90 degrees from the direction) with joins,
+    /// moving to the start for closed contour, or continuing the current point for open.
+    fn side(&self, c: &Contour<T>, hw: T, tol: T, pb: &mut PathBuilder<T>) {
+        let (pts, m) = (&c.pts, c.pts.len());
+        let segs = if c.closed { m } else { m - 1 };
+        let (dirs, lens): (Vec<_>, Vec<_>) = (0..segs).map(|i| {
+            let v = pts[(i + 1) % m] - pts[i];  (v.normalize().unwrap_or_default(), length(v))
+        }).unzip();
+
+        //  the inner join point if the offset lines intersect within halves of both segments
+        let inner = |j: usize, a: usize, b: usize| {
+            let (ua, ub) = (dirs[a], dirs[b]);  let (cos, sin) = (ua.dot(ub), ua.cross(ub));
+            if sin <= T::ZERO { return None }
+            let d = hw * sin / (T::ONE + cos);
+            (d <= lens[a] * T::HALF && d <= lens[b] * T::HALF)
+                .then(|| pts[j] + ua.perp() * hw - ua * d)
+        };
+
+        if c.closed {
+            pb.move_to(inner(0, segs - 1, 0).unwrap_or(pts[0] + dirs[0].perp() * hw));
+        } else if pb.current_point() != pts[0] + dirs[0].perp() * hw {
+            pb.move_to(pts[0] + dirs[0].perp() * hw);
+        }
+
+        for i in 0..segs {
+            let (j, k) = ((i + 1) % m, (i + 1) % segs);
+            if !c.closed && i + 1 == segs { pb.line_to(pts[j] + dirs[i].perp() * hw); break }
+            if let Some(pt) = inner(j, i, k) { pb.line_to(pt); continue }
+
+            let (p, ua, ub) = (pts[j], dirs[i], dirs[k]);
+            let (na, nb) = (ua.perp() * hw, ub.perp() * hw);
+            let (cos, sin) = (ua.dot(ub), ua.cross(ub));
+            pb.line_to(p + na);
+            if T::ZERO < sin { pb.line_to(p).line_to(p + nb); continue }    // inner pivot
+            if sin == T::ZERO && T::ZERO < cos { continue }     // straight
+
+            let (cos_half, sin_half) = (((T::ONE + cos) * T::HALF).sqrt(),
+                ((T::ONE - cos) * T::HALF).sqrt());
+            //  only where the turn isn't 180 degrees
+            let miter = || p + (na + nb) / (T::ONE + cos);
+            match if c.smooth[j] { LineJoin::Round } else { self.join } {
+                LineJoin::Bevel => (),
+                LineJoin::Miter | LineJoin::MiterClip if T::ONE <= cos_half * self.miter_limit => {
+                    pb.line_to(miter()); }
+                LineJoin::Miter => (),
+                LineJoin::MiterClip => {
+                    let t = hw * (self.miter_limit - cos_half) / sin_half;
+                    pb.line_to(p + na + ua * t).line_to(p + nb - ub * t);
+                }
+                //  miter is close enough to the arc
+                LineJoin::Round if hw * (T::ONE - cos_half) <= tol * cos_half => {
+                    pb.line_to(miter()); }
+                LineJoin::Round => {
+                    pb.arc_to(Vector::new(hw, hw), T::ZERO, false, false, p + nb);  continue }
+            }   pb.line_to(p + nb);
+        }
+    }
+}
@@ -0,0 +1,262 @@
+
+//  https://www.w3.org/TR/SVG/paths.html#PathDataBNF
+//  https://www.w3.org/TR/SVG/paths.html#PathDataErrorHandling
+//  Elliptical arcs are converted by `PathBuilder::arc_to` (endpoint to center parameterization),
+//  and conics are written out as quads since SVG path data has no rational curves,
+//  subdivided by the error estimate of Skia's `SkConic::computeQuadPOW2`.
+
+use core::fmt::{self, Display, Write};
+use crate::{geometry::{flatten::length, Path, PathBuilder, PathCmd, Point, Segment, Vector},
+    math::Scalar};
+
+/// Maximum number of subdivisions (in power of 2) of a conic into quads.
+const MAX_CONIC_POW2: i32 = 5;
+
+/// Kinds of errors in SVG path data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)] pub enum SvgErrorKind {
+    /// path data doesn't start with `M`/`m`
+    MissingMoveTo,
+    InvalidCommand(char),
+    InvalidNumber,
+    /// arc flag isn't `0` or `1`
+    InvalidFlag,
+}
+
+/// Error in SVG path data, at the byte position of the string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)] pub struct SvgError {
+    pub pos: usize, pub kind: SvgErrorKind,
+}
+
+impl Display for SvgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            SvgErrorKind::MissingMoveTo => write!(f, "expected moveto"),
+            SvgErrorKind::InvalidCommand(c) => write!(f, "invalid command '{c}'"),
+            SvgErrorKind::InvalidNumber => write!(f, "invalid number"),
+            SvgErrorKind::InvalidFlag   => write!(f, "invalid flag"),
+        }?;     write!(f, " at position {}", self.pos)
+    }
+}
+
+impl std::error::Error for SvgError {}
+
+struct Lexer<'a> { s: &'a [u8], pos: usize }
+
+impl Lexer<'_> {
+    #[inline] fn peek(&self) -> Option<u8> { self.s.get(self.pos).copied() }
+    #[inline] fn error(&self, kind: SvgErrorKind) -> SvgError { SvgError { pos: self.pos, kind } }
+
+    fn skip_wsp(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r' | b'\x0C')) { self.pos += 1 }
+    }
+    fn skip_wsp_comma(&mut self) {
+        self.skip_wsp();    if self.peek() == Some(b',') { self.pos += 1;  self.skip_wsp(); }
+    }
+
+    /// Whether a number follows, to repeat the previous command implicitly.
+    fn at_number(&self) -> bool { matches!(self.peek(), Some(b'0'..=b'9' | b'.' | b'+' | b'-')) }
+
+    fn number<T: Scalar>(&mut self) -> Result<T, SvgError> {
+        let (s, start) = (self.s, self.pos);
+        let digits = |pos: &mut usize| {
+            let begin = *pos;   while s.get(*pos).is_some_and(u8::is_ascii_digit) { *pos += 1 }
+            begin < *pos
+        };
+
+        let mut pos = start;
+        if matches!(s.get(pos), Some(b'+' | b'-')) { pos += 1 }
+        let mut valid = digits(&mut pos);
+        if s.get(pos) == Some(&b'.') { pos += 1;    valid |= digits(&mut pos); }
+        if !valid { return Err(self.error(SvgErrorKind::InvalidNumber)) }
+
+        if matches!(s.get(pos), Some(b'e' | b'E')) {    // exponent only if followed by digits
+            let mut exp = pos + 1;
+            if matches!(s.get(exp), Some(b'+' | b'-')) { exp += 1 }
+            if digits(&mut exp) { pos = exp }
+        }
+
+        let v = core::str::from_utf8(&s[start..pos]).ok().and_then(|v| v.parse::<f64>().ok())
+            .ok_or(self.error(SvgErrorKind::InvalidNumber))?;
+        self.pos = pos;     self.skip_wsp_comma();  Ok(T::from_f64(v))
+    }
+
+    fn point<T: Scalar>(&mut self) -> Result<Point<T>, SvgError> {
+        Ok(Point::new(self.number()?, self.number()?))
+    }
+
+    fn flag(&mut self) -> Result<bool, SvgError> {
+        let flag = match self.peek() { Some(b'0') => false, Some(b'1') => true,
+            _ => return Err(self.error(SvgErrorKind::InvalidFlag)) };
+        self.pos += 1;  self.skip_wsp_comma();  Ok(flag)
+    }
+}
+
+impl<T: Scalar> Path<T> {
+    /** Parse SVG path data, with all commands in absolute and relative forms,
+        implicit repeats of commands and compact numbers (e.g. `M.5.5-1-1`).
+    ```
+        use ugl_rs::geometry::*;
+        let p = |x, y| Point::new(x, y);
+        let path = Path::from_svg("M10 20 30,40 h5v-5 z m1 1").unwrap();
+        assert_eq!(path.verbs(), &[Verb::Move, Verb::Line, Verb::Line, Verb::Line,
+            Verb::Close, Verb::Move]);
+        assert_eq!(path.points(), &[p(10., 20.), p(30., 40.), p(35., 40.), p(35., 35.),
+            p(11., 21.)]);
+        let path = Path::from_svg("M.5.5-1-1e1").unwrap();
+        assert_eq!(path.points(), &[p(0.5, 0.5), p(-1., -10.)]);
+
+        let path = Path::from_svg("M0 0c0 10 10 10 10 0s10-10 10 0Q25 5 30 0t10 0").unwrap();
+        let reflected: Vec<_> = path.iter().skip(2).collect();
+        assert_eq!(reflected, [PathCmd::CubicTo(p(10., -10.), p(20., -10.), p(20., 0.)),
+            PathCmd::QuadTo(p(25., 5.), p(30., 0.)), PathCmd::QuadTo(p(35., -5.), p(40., 0.))]);
+
+        let arc: Path = Path::from_svg("M0 0a5 5 0 0010 0").unwrap();    // compact flags
+        let rc = arc.tight_bounds().unwrap();
+        assert!(rc.top.abs() < 1e-5 && (rc.bottom - 5.).abs() < 1e-5);
+        assert_eq!(arc.points().last(), Some(&p(10., 0.)));
+
+        let err = |s| Path::<f32>::from_svg(s).unwrap_err();
+        assert_eq!(err("L1 2"), SvgError { pos: 0, kind: SvgErrorKind::MissingMoveTo });
+        assert_eq!(err("M1 2 X"), SvgError { pos: 5, kind: SvgErrorKind::InvalidCommand('X') });
+        assert_eq!(err("M1 2 X 3"), SvgError { pos: 5, kind: SvgErrorKind::InvalidCommand('X') });
+        assert_eq!(err("M10"), SvgError { pos: 3, kind: SvgErrorKind::InvalidNumber });
+        assert_eq!(err("M0 0A1 1 0 2 1 2 2").pos, 11);
+        assert_eq!(err("M1 2 L3 -").to_string(), "invalid number at position 8");
+        assert!(Path::<f32>::from_svg(" ").unwrap().is_empty());
+    ``` */
+    pub fn from_svg(data: &str) -> Result<Self, SvgError> {
+        let mut lex = Lexer { s: data.as_bytes(), pos: 0 };
+        let mut pb = PathBuilder::new();
+        let mut ctrl = None;    // the last control point of cubic/quad for reflection
+        let (mut cmd, mut cmd_pos) = (b' ', 0);
+        lex.skip_wsp();
+
+        while let Some(c) = lex.peek() {
+            if c.is_ascii_alphabetic() {
+                if cmd == b' ' && !matches!(c, b'M' | b'm') {
+                    return Err(lex.error(SvgErrorKind::MissingMoveTo))
+                }   (cmd, cmd_pos) = (c, lex.pos);  lex.pos += 1;   lex.skip_wsp();
+            } else if !lex.at_number() || matches!(cmd, b' ' | b'Z' | b'z') {
+                let kind = if cmd == b' ' { SvgErrorKind::MissingMoveTo } else {
+                    SvgErrorKind::InvalidCommand(data[lex.pos..].chars().next().unwrap()) };
+                return Err(lex.error(kind))
+            }
+
+            let rel = cmd.is_ascii_lowercase();
+            let last = pb.current_point();
+            let base = if rel { last } else { Point::zero() };
+            let reflect = |kind: u8| match ctrl {
+                Some((k, c)) if k == kind => last * T::TWO - c, _ => last };
+
+            ctrl = match cmd.to_ascii_uppercase() {
+                b'M' => { pb.move_to(base + lex.point()?);
+                    cmd = if rel { b'l' } else { b'L' };    None    // repeated as lineto
+                }
+                b'L' => { pb.line_to(base + lex.point()?);  None }
+                b'H' => { let x = lex.number()?;
+                    pb.line_to(Point::new(if rel { last.x + x } else { x }, last.y));  None
+                }
+                b'V' => { let y = lex.number()?;
+                    pb.line_to(Point::new(last.x, if rel { last.y + y } else { y }));  None
+                }
+                b'C' => {
+                    let (c1, c2) = (base + lex.point()?, base + lex.point()?);
+                    let p = base + lex.point()?;
+                    pb.cubic_to(c1, c2, p);     Some((b'C', c2))
+                }
+                b'S' => {   let c1 = reflect(b'C');
+                    let (c2, p) = (base + lex.point()?, base + lex.point()?);
+                    pb.cubic_to(c1, c2, p);     Some((b'C', c2))
+                }
+                b'Q' => { let (c, p) = (base + lex.point()?, base + lex.point()?);
+                    pb.quad_to(c, p);   Some((b'Q', c))
+                }
+                b'T' => { let c = reflect(b'Q');
+                    pb.quad_to(c, base + lex.point()?);     Some((b'Q', c))
+                }
+                b'A' => {
+                    let radii = Vector::new(lex.number()?, lex.number()?);
+                    let angle = T::from_f64(lex.number::<T>()?.to_f64().to_radians());
+                    let (large_arc, sweep) = (lex.flag()?, lex.flag()?);
+                    pb.arc_to(radii, angle, large_arc, sweep, base + lex.point()?);     None
+                }
+                b'Z' => { pb.close();  None }
+                _ => return Err(SvgError { pos: cmd_pos,
+                    kind: SvgErrorKind::InvalidCommand(cmd as char) }),
+            };  lex.skip_wsp_comma();
+        }   Ok(pb.build())
+    }
+
+    /** Write out as SVG path data in absolute commands, numbers in the shortest forms
+        to read back exactly, and conics are converted to quads within 1/10000 of their sizes
+        (exact for parabola).
+    ```
+        use ugl_rs::geometry::*;
+        let path: Path = Path::from_svg("M1 2L3.5 -4Q5 6 7 8C1 2 3 4 5 6Z").unwrap();
+        assert_eq!(path.to_svg(), "M1 2L3.5 -4Q5 6 7 8C1 2 3 4 5 6Z");
+        assert_eq!(Path::from_svg(&path.to_svg()).unwrap(), path);
+
+        let circle = Path::from_svg(&Path::circle(Point::new(0., 0.), 10.).to_svg()).unwrap();
+        assert!(circle.verbs().iter().all(|v| matches!(v, Verb::Move | Verb::Quad | Verb::Close)));
+        assert!(circle.segments().all(|seg| (0..=10).all(|k|
+            (seg.eval(k as f32 / 10.).length() - 10f32).abs() < 1e-3)));
+
+        let mut pb = PathBuilder::new();    // hyperbolic
+        pb.move_to(Point::new(0., 0.)).conic_to(Point::new(50., 100.), Point::new(100., 0.), 4.);
+        let conic = pb.build();     let read = Path::from_svg(&conic.to_svg()).unwrap();
+        assert!((1..10).all(|k| read.distance(conic.segments().next().unwrap()
+            .eval(k as f32 / 10.)).unwrap() < 0.02));
+
+        let precise = Path::<f64>::from_svg("M123456.789012 0.1L0 0").unwrap();
+        assert_eq!(precise.to_svg(), "M123456.789012 0.1L0 0");
+    ``` */
+    pub fn to_svg(&self) -> String {
+        let mut s = String::new();
+        let mut put = |cmd: char, pts: &[Point<T>]| {
+            //  the shorter form of f32 if exact, e.g. for `Path<f32>`
+            let num = |s: &mut String, v: T| { let v = v.to_f64();
+                let _ = if v as f32 as f64 == v { write!(s, "{}", v as f32) }
+                    else { write!(s, "{v}") };
+            };
+
+            s.push(cmd);
+            for (i, pt) in pts.iter().enumerate() {
+                if 0 < i { s.push(' ') }
+                num(&mut s, pt.x);  s.push(' ');    num(&mut s, pt.y);
+            }
+        };
+
+        let mut last = Point::zero();
+        for cmd in self.iter() {
+            match cmd {
+                PathCmd::MoveTo(p) => put('M', &[p]),
+                PathCmd::LineTo(p) => put('L', &[p]),
+                PathCmd::QuadTo(c, p) => put('Q', &[c, p]),
+                PathCmd::CubicTo(c1, c2, p) => put('C', &[c1, c2, p]),
+                PathCmd::ConicTo(_, p, w) if w <= T::ZERO => put('L', &[p]),
+                PathCmd::ConicTo(c, p, w) => {
+                    //  the error is reduced by 4 for each subdivision in halves
+                    let tol = length(c - last).max(length(p - c)) / T::from_i32(10000);
+                    let a = w - T::ONE;
+                    let mut err = length(last - c * T::TWO + p) *
+                        (a / (T::from_i32(4) * (T::TWO + a))).abs();
+                    let mut pow = 0;
+                    while tol < err && pow < MAX_CONIC_POW2 { err /= T::from_i32(4);   pow += 1 }
+
+                    let mut quads = vec![Segment::Conic([last, c, p], w)];
+                    for _ in 0..pow { quads = quads.iter().flat_map(|seg| {
+                        let (a, b) = seg.split(T::HALF);  [a, b] }).collect() }
+                    for seg in quads {
+                        if let Segment::Conic([_, c, p], _) = seg { put('Q', &[c, p]) }
+                    }
+                }
+                PathCmd::Close => put('Z', &[]),
+            }
+            last = match cmd {
+                PathCmd::MoveTo(p) | PathCmd::LineTo(p) | PathCmd::QuadTo(_, p) |
+                PathCmd::CubicTo(_, _, p) | PathCmd::ConicTo(_, p, _) => p,
+                PathCmd::Close => last,     // no drawing after close without move
+            };
+        }   s
+    }
+}
@@ -0,0 +1,278 @@
+
+//  Tessellation of filled paths into triangles, after cleaned by the boolean union
+//  (resolving fill rules, overlaps and self-intersections into contours with holes,
+//  the inside on the left) and flattened:
+//  https://en.wikipedia.org/wiki/Polygon_triangulation
+//  https://www.cs.umd.edu/class/spring2020/cmsc754/Lects/lect05-triang.pdf
+//  - simple polygons of few vertices are triangulated by ear clipping;
+//  - others (with holes, or many vertices) are partitioned into y-monotone pieces by
+//    sweep-line (de Berg et al. "Computational Geometry" chapter 3), then triangulated
+//    by the stack of reflex chain in linear time.
+
+use std::collections::HashMap;
+use crate::{geometry::{BoolOp, Path, PathCmd, Point},
+    math::{Scalar, predicates::orient2d}};
+
+/// Vertex count limit of simple polygons to be triangulated by ear clipping.
+const EAR_CLIPPING_MAX: usize = 64;
+
+/** Indexed triangle mesh, with triangles counter-clockwise in y-up coordinates
+    (i.e. clockwise in y-down) as the orientation of `Path::boolean` output.
+```
+    use ugl_rs::{geometry::*, math::predicates::orient2d};
+    let area = |mesh: &Mesh| mesh.indices.chunks(3).map(|t| {
+        let (a, b, c) = (mesh.vertices[t[0] as usize], mesh.vertices[t[1] as usize],
+            mesh.vertices[t[2] as usize]);
+        assert!(0. < orient2d(a, b, c));    (b - a).cross(c - a) / 2.
+    }).sum::<f32>();
+
+    let mesh = Path::rect(Rect::new(0., 0., 2., 2.)).tessellate(0.1, None);
+    assert_eq!((mesh.vertices.len(), mesh.indices.len()), (4, 6));     assert_eq!(area(&mesh), 4.);
+
+    let pts = [(0., 0.), (2., 0.), (2., 1.), (1., 1.), (1., 2.), (0., 2.)];   // L-shape
+    let path = Path::polyline(&pts.map(Point::from), true);
+    let mesh = path.tessellate(0.1, None);
+    assert_eq!(mesh.indices.len(), 4 * 3);  assert_eq!(area(&mesh), 3.);
+
+    let mut pb = PathBuilder::new();    pb.fill_rule(FillRule::EvenOdd);    // with hole
+    pb.move_to(Point::new(0., 0.)).line_to(Point::new(4., 0.)).line_to(Point::new(4., 4.))
+      .line_to(Point::new(0., 4.)).close().move_to(Point::new(1., 1.))
+      .line_to(Point::new(3., 1.)).line_to(Point::new(3., 3.)).line_to(Point::new(1., 3.))
+      .close();
+    let mesh = pb.build().tessellate(0.1, None);
+    assert_eq!(mesh.indices.len(), 8 * 3);  assert_eq!(area(&mesh), 12.);
+
+    let mesh = Path::circle(Point::new(0., 0.), 10.).tessellate(0.01, None);
+    assert!(64 < mesh.vertices.len());    // by sweep-line
+    assert!((area(&mesh) - 100. * core::f32::consts::PI).abs() < 0.5);
+
+    let mesh = Path::rect(Rect::new(0., 0., 2., 2.)).tessellate(0.1, Some(1.));
+    assert_eq!(mesh.vertices.len(), 8);     assert_eq!(mesh.indices.len(), (2 + 8) * 3);
+    assert_eq!(mesh.coverage, [1., 1., 1., 1., 0., 0., 0., 0.]);
+    assert!(mesh.vertices.contains(&Point::new(-1., -1.)));
+``` */
+#[derive(Clone, Debug, Default, PartialEq)] pub struct Mesh<T: Scalar = f32> {
+    pub vertices: Vec<Point<T>>,
+    /// coverage of vertices for anti-aliasing, 0 at the outer edge of the fringe and 1 else
+    pub coverage: Vec<T>,
+    /// triangles of vertex indices
+    pub indices: Vec<u32>,
+}
+
+type P = Point<f64>;
+
+impl<T: Scalar> Path<T> {
+    /// Tessellate the filled region (under its fill rule) into triangles, with curves
+    /// flattened within the tolerance, and optionally with the anti-aliasing fringe
+    /// of the width outside the boundary.
+    pub fn tessellate(&self, tolerance: T, fringe: Option<T>) -> Mesh<T> {
+        let clean = self.boolean(&Path::default(), BoolOp::Union, tolerance).flatten(tolerance);
+        let (mut pts, mut contours) = (Vec::<P>::new(), Vec::<Vec<_>>::new());
+        let end_contour = |pts: &mut Vec<_>, contours: &mut Vec<Vec<_>>|
+            if let Some(contour) = contours.last_mut() {    // without repeated points
+                if 1 < contour.len() && pts[contour[0]] == pts[pts.len() - 1] {
+                    contour.pop();  pts.pop();
+                }
+                if contour.len() < 3 { pts.truncate(pts.len() - contour.len());  contours.pop(); }
+            };
+        for cmd in clean.iter() {
+            let p = match cmd {
+                PathCmd::MoveTo(p) => {
+                    end_contour(&mut pts, &mut contours);   contours.push(vec![]);  p
+                }
+                PathCmd::LineTo(p) => p,
+                PathCmd::Close => continue,
+                _ => unreachable!(),
+            };
+            let p = Point::new(p.x.to_f64(), p.y.to_f64());
+            let contour = contours.last_mut().unwrap();
+            if contour.last().is_some_and(|&i| pts[i] == p) { continue }
+            contour.push(pts.len());    pts.push(p);
+        }   end_contour(&mut pts, &mut contours);
+
+        let (mut prev, mut next) = (vec![0; pts.len()], vec![0; pts.len()]);
+        for contour in &contours {
+            for (k, &i) in contour.iter().enumerate() {
+                next[i] = contour[(k + 1) % contour.len()];  prev[next[i]] = i;
+            }
+        }
+
+        //  coincident vertices of contours touching (each other or itself) are separated slightly,
+        //  into the inside wedges of their corners, for the sweep-line to be consistent
+        let extent = pts.iter().fold(0f64, |m, p| m.max(p.x.abs()).max(p.y.abs()));
+        let eps = 2f64.powi(extent.max(f64::MIN_POSITIVE).log2().ceil() as i32 - 36);
+        let mut coincident = HashMap::<_, Vec<_>>::new();
+        pts.iter().enumerate().for_each(|(i, p)|
+            coincident.entry((p.x.to_bits(), p.y.to_bits())).or_default().push(i));
+        let shifts: Vec<_> = coincident.into_values().filter(|v| 1 < v.len()).flatten()
+            .filter_map(|i| {
+                let (u0, u1) = ((pts[prev[i]] - pts[i]).normalize()?,
+                    (pts[next[i]] - pts[i]).normalize()?);
+                let (turn, sum) = (u1.cross(u0), u1 + u0);
+                let d = if turn != 0. { sum * turn.signum() } else if u1.dot(u0) < 0. {
+                    Point::new(-u1.y, u1.x) } else { u1 };
+                Some((i, d.normalize()? * eps))
+            }).collect();
+        shifts.into_iter().for_each(|(i, d)| pts[i] += d);
+
+        let mut tris = Vec::new();
+        if contours.len() == 1 && contours[0].len() <= EAR_CLIPPING_MAX {
+            ear_clip(&pts, contours[0].clone(), &mut tris);
+        } else {
+            let diagonals = monotone_partition(&pts, &prev, &next);
+            for face in faces(&pts, &next, &diagonals) {
+                triangulate_monotone(&pts, &face, &mut tris)
+            }
+        }
+
+        let cv = |p: P| Point::new(T::from_f64(p.x), T::from_f64(p.y));
+        let vertices: Vec<_> = pts.iter().map(|&p| cv(p)).collect();
+        let indices = tris.iter().filter(|t: &&[usize; 3]|    // degenerated by rounding
+            0. < orient2d(vertices[t[0]], vertices[t[1]], vertices[t[2]]))
+            .flat_map(|t| t.map(|i| i as u32)).collect();
+        let mut mesh = Mesh { vertices, coverage: vec![T::ONE; pts.len()], indices };
+
+        if let Some(width) = fringe {   // outset along the miter of right normals
+            let (w, n) = (width.to_f64(), pts.len());
+            let normal = |a: P, b: P| { let d = b - a;  Point::new(d.y, -d.x) / d.length() };
+            for i in 0..n {
+                let (n0, n1) = (normal(pts[prev[i]], pts[i]), normal(pts[i], pts[next[i]]));
+                let o = pts[i] + (n0 + n1) * (w / (1. + n0.dot(n1)).max(0.25));
+                mesh.vertices.push(cv(o));  mesh.coverage.push(T::ZERO);
+                let (j, oi, oj) = (next[i] as u32, (n + i) as u32, (n + next[i]) as u32);
+                mesh.indices.extend_from_slice(&[i as u32, oi, oj, i as u32, oj, j]);
+            }
+        }   mesh
+    }
+}
+
+/// Triangulate the simple polygon (counter-clockwise) by clipping ears.
+fn ear_clip(pts: &[P], mut poly: Vec<usize>, tris: &mut Vec<[usize; 3]>) {
+    let inside = |p: P, a: P, b: P, c: P|
+        0. <= orient2d(a, b, p) && 0. <= orient2d(b, c, p) && 0. <= orient2d(c, a, p);
+    while 3 < poly.len() {
+        let n = poly.len();
+        let ear = (0..n).find_map(|i| {
+            let (a, b, c) = (poly[(i + n - 1) % n], poly[i], poly[(i + 1) % n]);
+            let turn = orient2d(pts[a], pts[b], pts[c]);
+            if turn == 0. { return Some((i, None)) }    // removed without triangle
+            (0. < turn && !poly.iter().any(|&k| ![pts[a], pts[b], pts[c]].contains(&pts[k]) &&
+                inside(pts[k], pts[a], pts[b], pts[c]))).then_some((i, Some([a, b, c])))
+        });
+
+        let Some((i, tri)) = ear else { break };    // degenerate
+        tris.extend(tri);   poly.remove(i);
+    }
+    if poly.len() == 3 && 0. < orient2d(pts[poly[0]], pts[poly[1]], pts[poly[2]]) {
+        tris.push([poly[0], poly[1], poly[2]]);
+    }
+}
+
+/// Diagonals to partition the polygon (with holes) into y-monotone pieces,
+/// by sweeping from top (greatest y, then least x) to bottom.
+fn monotone_partition(pts: &[P], prev: &[usize], next: &[usize]) -> Vec<(usize, usize)> {
+    let mut order: Vec<_> = (0..pts.len()).collect();
+    order.sort_by(|&i, &j| pts[j].y.total_cmp(&pts[i].y).then(pts[i].x.total_cmp(&pts[j].x)));
+    let mut rank = vec![0; pts.len()];
+    order.iter().enumerate().for_each(|(r, &i)| rank[i] = r);
+
+    #[derive(Clone, Copy, PartialEq)] enum Kind { Start, Split, End, Merge, Regular }
+    let kind = |v: usize| {
+        let (above_p, above_n) = (rank[prev[v]] < rank[v], rank[next[v]] < rank[v]);
+        let convex = 0. < orient2d(pts[prev[v]], pts[v], pts[next[v]]);
+        match (above_p, above_n) {
+            (false, false) => if convex { Kind::Start } else { Kind::Split },
+            (true,  true)  => if convex { Kind::End } else { Kind::Merge },
+            _ => Kind::Regular,
+        }
+    };
+
+    //  edges (by the start vertex) intersecting the sweep line, with their helpers
+    let (mut status, mut helper) = (Vec::<usize>::new(), vec![0; pts.len()]);
+    let x_at = |e: usize, y: f64| { let (a, b) = (pts[e], pts[next[e]]);
+        if a.y == b.y { a.x.max(b.x) } else { a.x + (b.x - a.x) * (y - a.y) / (b.y - a.y) } };
+    let left_of = |status: &[usize], v: usize| status.iter().copied()
+        .filter(|&e| e != v && next[e] != v && x_at(e, pts[v].y) <= pts[v].x)
+        .max_by(|&e, &f| x_at(e, pts[v].y).total_cmp(&x_at(f, pts[v].y)));
+
+    let mut diagonals = Vec::new();
+    for &v in &order {
+        let (p, k) = (prev[v], kind(v));
+        let down = rank[p] < rank[v];   // regular vertex with the inside on the right
+        if matches!(k, Kind::End | Kind::Merge) || k == Kind::Regular && down {
+            if kind(helper[p]) == Kind::Merge { diagonals.push((v, helper[p])) }
+            status.retain(|&e| e != p);
+        }
+        if matches!(k, Kind::Split | Kind::Merge) || k == Kind::Regular && !down {
+            if let Some(e) = left_of(&status, v) {
+                if k == Kind::Split || kind(helper[e]) == Kind::Merge {
+                    diagonals.push((v, helper[e]))
+                }   helper[e] = v;
+            }
+        }
+        if matches!(k, Kind::Start | Kind::Split) || k == Kind::Regular && down {
+            status.push(v);     helper[v] = v;
+        }
+    }   diagonals
+}
+
+/// Faces of the polygon split by the diagonals, tracing with the inside on the left
+/// by turning left most at the vertices.
+fn faces(pts: &[P], next: &[usize], diagonals: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut outgoing = HashMap::<usize, Vec<usize>>::new();
+    let mut edges: Vec<_> = (0..pts.len()).map(|i| (i, next[i])).collect();
+    diagonals.iter().for_each(|&(a, b)| edges.extend([(a, b), (b, a)]));
+    edges.iter().enumerate().for_each(|(k, &(a, _))| outgoing.entry(a).or_default().push(k));
+
+    let turn = |(a, b): (usize, usize), (_, c): (usize, usize)| {
+        let (d, v) = (pts[b] - pts[a], pts[c] - pts[b]);
+        if c == a { -core::f64::consts::PI } else { d.cross(v).atan2(d.dot(v)) }
+    };
+
+    let (mut used, mut faces) = (vec![false; edges.len()], Vec::new());
+    for s in 0..edges.len() {
+        if used[s] { continue }
+        let (mut face, mut k) = (Vec::new(), s);
+        while !used[k] {
+            used[k] = true;     face.push(edges[k].0);
+            k = *outgoing[&edges[k].1].iter().max_by(|&&i, &&j|
+                turn(edges[k], edges[i]).total_cmp(&turn(edges[k], edges[j]))).unwrap();
+        }   faces.push(face);
+    }   faces
+}
+
+/// Triangulate the y-monotone polygon (counter-clockwise).
+fn triangulate_monotone(pts: &[P], face: &[usize], tris: &mut Vec<[usize; 3]>) {
+    let m = face.len();     if m < 3 { return }
+    let above = |i: usize, j: usize| pts[face[j]].y.total_cmp(&pts[face[i]].y)
+        .then(pts[face[i]].x.total_cmp(&pts[face[j]].x));
+    let mut order: Vec<_> = (0..m).collect();   order.sort_by(|&i, &j| above(i, j));
+
+    //  the left chain goes down from the top vertex in counter-clockwise order
+    let (top, bottom) = (order[0], order[m - 1]);
+    let mut left = vec![false; m];
+    let mut k = top;    while k != bottom { left[k] = true;     k = (k + 1) % m; }
+
+    let mut emit = |a: usize, b: usize, c: usize| {
+        let (a, b, c) = (face[a], face[b], face[c]);
+        let turn = orient2d(pts[a], pts[b], pts[c]);
+        if 0. < turn { tris.push([a, b, c]) } else if turn < 0. { tris.push([a, c, b]) }
+    };
+
+    let mut stack = vec![order[0], order[1]];
+    for &u in &order[2..m - 1] {
+        if left[u] != left[*stack.last().unwrap()] {
+            stack.windows(2).for_each(|w| emit(u, w[0], w[1]));
+            stack = vec![*stack.last().unwrap(), u];
+        } else {
+            let mut last = stack.pop().unwrap();
+            while let Some(&top) = stack.last() {
+                let (p, q, r) = (pts[face[top]], pts[face[last]], pts[face[u]]);
+                let inside = if left[u] { orient2d(p, q, r) } else { orient2d(r, q, p) };
+                if inside <= 0. { break }
+                emit(u, last, top);     last = stack.pop().unwrap();
+            }   stack.push(last);   stack.push(u);
+        }
+    }
+    stack.windows(2).for_each(|w| emit(bottom, w[0], w[1]));
+}
@@ -0,0 +1,110 @@
+
+use crate::{blend::BlendMode, color::Pixel, geometry::IRect};
+
+/// Image/bitmap of pixels in any format, stored in row-major order.
+#[derive(Clone, Debug, PartialEq)] pub struct Image<P: Pixel> {
+    pub width: usize, pub height: usize, pub data: Vec<P>,
+}
+
+impl<P: Pixel> Image<P> {
+    pub fn new(width: usize, height: usize, fill: P) -> Self {
+        Self { width, height, data: vec![fill; width * height] }
+    }
+
+    pub fn from_vec(width: usize, height: usize, data: Vec<P>) -> Self {
+        assert_eq!(data.len(), width * height);     Self { width, height, data }
+    }
+
+    #[inline] pub fn bounds(&self) -> IRect {
+        IRect::new(0, 0, self.width as _, self.height as _)
+    }
+
+    #[inline] pub fn pixel(&self, x: usize, y: usize) -> P { self.data[y * self.width + x] }
+    #[inline] pub fn row(&self, y: usize) -> &[P] {
+        &self.data[y * self.width..][..self.width]
+    }
+    #[inline] pub fn row_mut(&mut self, y: usize) -> &mut [P] {
+        &mut self.data[y * self.width..][..self.width]
+    }
+
+    /// Convert into another pixel format.
+    pub fn convert<Q: Pixel>(&self) -> Image<Q> {
+        Image { width: self.width, height: self.height,
+            data: self.data.iter().map(|p| Q::from_color(p.to_color())).collect() }
+    }
+
+    /// Copy out the part within the rectangle, clipped by the image bounds.
+    pub fn crop(&self, rect: IRect) -> Self {
+        let Some(rect) = rect.intersect(&self.bounds()) else {
+            return Self { width: 0, height: 0, data: vec![] } };
+        let (x, w) = (rect.left as usize, rect.width() as usize);
+        Self { width: w, height: rect.height() as _, data: (rect.top..rect.bottom)
+            .flat_map(|y| &self.row(y as _)[x..x + w]).copied().collect() }
+    }
+}
+
+/** Composite the source image within `src_rect` onto `dst_rect` of the destination image,
+    with the blending mode and opacity. Pixels are converted to `Color` for span compositors.
+    It is scaled (by nearest sampling) if the sizes of the two rectangles differ,
+    and is clipped by bounds of both the destination and source images.
+```
+    use ugl_rs::{blend::BlendMode, color::RGBA, geometry::IRect, image::*};
+    let mut dst = Image::new(4, 4, RGBA::<u16>::black());
+    let src = Image::new(2, 2, RGBA::<f32>::red());
+
+    composite_image(&mut dst, IRect::new(-1, 3, 2, 2), &src, src.bounds(),
+        BlendMode::SrcOver, 255);
+    assert_eq!(dst.pixel(0, 3), RGBA::red());
+    assert_eq!(dst.pixel(1, 3), RGBA::black());
+    assert_eq!(dst.pixel(0, 2), RGBA::black());
+
+    let mut dst = Image::new(4, 2, 0u8);    // gray
+    composite_image(&mut dst, IRect::new(0, 0, 4, 2), &src, IRect::new(0, 0, 2, 1),
+        BlendMode::SrcOver, 255);           // scale up
+    assert_eq!(dst.data, [77; 8]);
+
+    let mut img = Image::from_vec(3, 1, vec![10u8, 20, 30]);
+    composite_image_within(&mut img, IRect::new(1, 0, 2, 1), IRect::new(0, 0, 2, 1),
+        BlendMode::Copy, 255);              // overlapped
+    assert_eq!(img.data, [10, 10, 20]);
+``` */
+pub fn composite_image<D: Pixel, S: Pixel>(dst: &mut Image<D>, dst_rect: IRect,
+    src: &Image<S>, src_rect: IRect, mode: BlendMode, opacity: u8) {
+    if dst_rect.is_empty() || src_rect.is_empty() { return }
+    let Some(clip) = dst_rect.intersect(&dst.bounds()) else { return };
+
+    //  map the center of destination pixel to the source, None if out of the source
+    let map = |d: i32, d0: i32, dn: i32, s0: i32, sn: i32, lim: usize| {
+        let s = s0 as i64 + ((d - d0) as i64 * 2 + 1) * sn as i64 / (dn as i64 * 2);
+        (0 <= s && s < lim as i64).then_some(s as usize)
+    };
+
+    let cols = (clip.left..clip.right).filter_map(|x| map(x, dst_rect.left,
+        dst_rect.width(), src_rect.left, src_rect.width(), src.width)
+        .map(|sx| (x as usize, sx))).collect::<Vec<_>>();
+    let Some(&(x0, _)) = cols.first() else { return };  // contiguous as monotonic
+
+    let comp = mode.compositor(opacity);
+    let (mut sbuf, mut dbuf) = (Vec::with_capacity(cols.len()), Vec::with_capacity(cols.len()));
+    for y in clip.top..clip.bottom {
+        let Some(sy) = map(y, dst_rect.top, dst_rect.height(),
+            src_rect.top, src_rect.height(), src.height) else { continue };
+
+        let srow = src.row(sy);
+        sbuf.clear();   sbuf.extend(cols.iter().map(|&(_, sx)| srow[sx].to_color()));
+        let drow = &mut dst.row_mut(y as _)[x0..x0 + cols.len()];
+        dbuf.clear();   dbuf.extend(drow.iter().map(|p| p.to_color()));
+
+        comp(&mut dbuf, &sbuf);
+        drow.iter_mut().zip(&dbuf).for_each(|(p, c)| *p = D::from_color(*c));
+    }
+}
+
+/// Composite a part of the image onto (probably overlapped) another part of itself.
+pub fn composite_image_within<P: Pixel>(img: &mut Image<P>, dst_rect: IRect,
+    src_rect: IRect, mode: BlendMode, opacity: u8) {
+    let Some(part) = src_rect.intersect(&img.bounds()) else { return };
+    let src = img.crop(part);
+    composite_image(img, dst_rect, &src, src_rect.translate(-part.left, -part.top),
+        mode, opacity)
+}
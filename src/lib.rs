@@ -4,6 +4,7 @@ pub mod math;       // linear algebra, affine transformation, trigonometry
 
 pub mod color;      // rgba/rgb, intensity & quantization
 pub mod blend;      // color blending & alpha compositing, gamma correction
+pub mod image;      // bitmap of pixel formats, image compositing
 
 pub mod sampler;    // can be thought of 2D shaders
 pub mod shader;     // 3D shaders
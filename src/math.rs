@@ -6,4 +6,132 @@ pub use nalgebra_glm::TMat;
 //  https://johnmcfarlane.github.io/cnl/
 //  https://gitlab.com/tspiteri/fixed
 #[cfg(feature = "fixed")] pub use fixed::traits::Fixed;
+#[cfg(feature = "fixed")] pub use fixed::types::{I16F16, I24F8};
 
+pub mod transform;  // 2D affine transformation
+pub mod approx;     // fast approximations of trigonometry, sqrt, exp/log
+pub mod projection; // 3D projection, view and viewport
+pub mod predicates; // robust geometric predicates: orientation, incircle, intersection
+pub mod quaternion; // quaternion for 3D rotations
+pub mod camera;     // orbit, first-person and trackball camera controllers
+pub use transform::Transform2D;
+
+use core::fmt::Debug;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/** Generic number type for geometry and rasterization, to be compiled for
+    both FPU (f32/f64) and FPU-less (fixed-point, e.g. Q16.16/Q24.8) targets from the same source.
+```
+    use ugl_rs::math::Scalar;
+    fn hypot<T: Scalar>(x: T, y: T) -> T { (x * x + y * y).sqrt() }
+
+    assert_eq!(hypot(3f32, 4.), 5.);
+    assert_eq!(hypot(3f64, 4.), 5.);
+    assert_eq!(Scalar::fract(-2.5f32), 0.5);    // NOT the same as the inherent `f32::fract`
+    assert_eq!(Scalar::to_i32(-2.5f32), -3);
+
+    #[cfg(feature = "fixed")] {     use ugl_rs::math::{I16F16, I24F8};
+        assert_eq!(hypot(I16F16::from_num(3), I16F16::from_num(4)), 5);
+        assert_eq!(hypot(I24F8 ::from_num(3), I24F8 ::from_num(4)), 5);
+        assert_eq!(I16F16::from_f32(-2.5).fract(), 0.5);
+        assert_eq!(I16F16::from_f32(-2.5).to_i32(), -3);
+        assert_eq!(I16F16::from_i32(7).to_f64(), 7.);
+        assert_eq!(Scalar::max(I16F16::HALF, I16F16::ONE), 1);
+    }
+``` */
+pub trait Scalar: Copy + Default + PartialEq + PartialOrd + Debug +
+    Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + Div<Output = Self> +
+    Neg<Output = Self> + AddAssign + SubAssign + MulAssign + DivAssign {
+    const ZERO: Self;   const ONE: Self;    const TWO: Self;    const HALF: Self;
+    /// the smallest positive increment (fixed-point), or the machine epsilon (float)
+    const EPSILON: Self;
+    const MAX: Self;    const MIN: Self;
+
+    fn from_f32(v: f32) -> Self;    fn to_f32(self) -> f32;
+    fn from_f64(v: f64) -> Self;    fn to_f64(self) -> f64;
+    fn from_i32(v: i32) -> Self;
+    /// Convert to integer, rounding towards negative infinity.
+    fn to_i32(self) -> i32;
+
+    fn sqrt (self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil (self) -> Self;
+    fn round(self) -> Self;
+    fn abs  (self) -> Self;
+    /// `self - self.floor()`, always non-negative.
+    #[inline] fn fract(self) -> Self { self - self.floor() }
+    #[inline] fn recip(self) -> Self { Self::ONE / self }
+
+    /// Sine and cosine of the angle in radians.
+    fn sin_cos(self) -> (Self, Self);
+    /// Four-quadrant arctangent of `self` (y) and `x` in radians, in the range of [-π, π].
+    fn atan2(self, x: Self) -> Self;
+
+    #[inline] fn min(self, other: Self) -> Self { if other < self { other } else { self } }
+    #[inline] fn max(self, other: Self) -> Self { if self < other { other } else { self } }
+    #[inline] fn clamp(self, min: Self, max: Self) -> Self { self.max(min).min(max) }
+    #[inline] fn lerp(self, other: Self, t: Self) -> Self { self + (other - self) * t }
+}
+
+macro_rules! impl_scalar_float { ($($t:ty),*) => { $(
+    impl Scalar for $t {
+        const ZERO: Self = 0.;  const ONE: Self = 1.;   const TWO: Self = 2.;
+        const HALF: Self = 0.5; const EPSILON: Self = <$t>::EPSILON;
+        const MAX:  Self = <$t>::MAX;   const MIN: Self = <$t>::MIN;
+
+        #[inline] fn from_f32(v: f32) -> Self { v as _ }
+        #[inline] fn   to_f32(self) -> f32 { self as _ }
+        #[inline] fn from_f64(v: f64) -> Self { v as _ }
+        #[inline] fn   to_f64(self) -> f64 { self as _ }
+        #[inline] fn from_i32(v: i32) -> Self { v as _ }
+        #[inline] fn   to_i32(self) -> i32 { <$t>::floor(self) as _ }
+
+        #[inline] fn sqrt (self) -> Self { <$t>::sqrt (self) }
+        #[inline] fn floor(self) -> Self { <$t>::floor(self) }
+        #[inline] fn ceil (self) -> Self { <$t>::ceil (self) }
+        #[inline] fn round(self) -> Self { <$t>::round(self) }
+        #[inline] fn abs  (self) -> Self { <$t>::abs  (self) }
+        #[inline] fn sin_cos(self) -> (Self, Self) { <$t>::sin_cos(self) }
+        #[inline] fn atan2(self, x: Self) -> Self { <$t>::atan2(self, x) }
+        #[inline] fn min(self, other: Self) -> Self { <$t>::min(self, other) }
+        #[inline] fn max(self, other: Self) -> Self { <$t>::max(self, other) }
+    }
+)* } }
+
+impl_scalar_float!(f32, f64);
+
+//  XXX: overflow panics in debug build and wraps in release build as the integers do
+#[cfg(feature = "fixed")] macro_rules! impl_scalar_fixed { ($($t:ty),*) => { $(
+    impl Scalar for $t {
+        const ZERO: Self = Self::ZERO;  const ONE: Self = Self::ONE;
+        const TWO:  Self = Self::const_from_int(2);
+        const HALF: Self = Self::from_bits(1 << (Self::FRAC_NBITS - 1));
+        const EPSILON: Self = Self::DELTA;
+        const MAX:  Self = Self::MAX;   const MIN: Self = Self::MIN;
+
+        #[inline] fn from_f32(v: f32) -> Self { Self::saturating_from_num(v) }
+        #[inline] fn   to_f32(self) -> f32 { self.to_num() }
+        #[inline] fn from_f64(v: f64) -> Self { Self::saturating_from_num(v) }
+        #[inline] fn   to_f64(self) -> f64 { self.to_num() }
+        #[inline] fn from_i32(v: i32) -> Self { Self::saturating_from_num(v) }
+        #[inline] fn   to_i32(self) -> i32 { self.to_num() }
+
+        #[inline] fn sqrt (self) -> Self { <$t>::sqrt (self) }
+        #[inline] fn floor(self) -> Self { <$t>::floor(self) }
+        #[inline] fn ceil (self) -> Self { <$t>::ceil (self) }
+        #[inline] fn round(self) -> Self { <$t>::round(self) }
+        #[inline] fn abs  (self) -> Self { <$t>::abs  (self) }
+        #[inline] fn fract(self) -> Self { <$t>::frac (self) }
+
+        #[inline] fn sin_cos(self) -> (Self, Self) {
+            let (s, c) = approx::sin_cos(I16F16::saturating_from_num(self));
+            (Self::saturating_from_num(s), Self::saturating_from_num(c))
+        }
+        #[inline] fn atan2(self, x: Self) -> Self {
+            let a = approx::atan2_bits(self.to_bits() as _, x.to_bits() as _);
+            Self::saturating_from_num(I16F16::from_bits(a))
+        }
+    }
+)* } }
+
+#[cfg(feature = "fixed")] impl_scalar_fixed!(I16F16, I24F8);
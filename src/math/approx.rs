@@ -0,0 +1,249 @@
+
+//  Fast approximations of elementary functions: fixed-point (Q16.16) ones for FPU-less
+//  targets are in pure integer math by LUT, CORDIC, polynomial and Newton-Raphson iterations,
+//  and float ones are by bit manipulation of IEEE 754 representation.
+
+#[cfg(feature = "fixed")] use crate::math::I16F16;
+
+/// Quarter sine wave in Q16.16 of 256 steps, with 2 guards for the interpolation.
+#[cfg(feature = "fixed")] const SIN_LUT: [i32; 258] = {
+    const fn sin(x: f64) -> f64 {   // Taylor series converges well in [0, π/2]
+        let (mut sum, mut term, mut k) = (x, x, 1);
+        while k < 12 { term *= -x * x / ((2 * k) * (2 * k + 1)) as f64; sum += term; k += 1 }
+        sum
+    }
+
+    let (mut lut, mut i) = ([0; 258], 0);
+    while i < lut.len() {
+        lut[i] = (sin(i as f64 * core::f64::consts::FRAC_PI_2 / 256.) * 65536. + 0.5) as i32;
+        i += 1;
+    }   lut
+};
+
+/// atan(2^-i) in Q2.30 for CORDIC
+#[cfg(feature = "fixed")] const ATAN_LUT: [i64; 30] = [
+    843314857, 497837829, 263043837, 133525159, 67021687, 33543516, 16775851, 8388437,
+    4194283, 2097149, 1048576, 524288, 262144, 131072, 65536, 32768, 16384, 8192, 4096,
+    2048, 1024, 512, 256, 128, 64, 32, 16, 8, 4, 2,
+];
+
+#[cfg(feature = "fixed")] const PI_Q30: i64 = 3373259426;
+
+/// Evaluate polynomial of coefficients (from the constant term) in Q2.30 by Horner's method.
+#[cfg(feature = "fixed")] #[inline] fn poly_q30(coef: &[i64], t: i64) -> i64 {
+    coef.iter().rev().fold(0, |acc, &c| ((acc * t) >> 30) + c)
+}
+
+#[cfg(feature = "fixed")] #[inline] fn saturate(v: i64) -> I16F16 {
+    I16F16::from_bits(v.clamp(i32::MIN as _, i32::MAX as _) as _)
+}
+
+/** Sine and cosine by quarter-wave LUT with linear interpolation,
+    the absolute error is less than 2^-15 (about 3.1e-5) for angles in ±8192 radians.
+```
+    use ugl_rs::math::{approx, I16F16};
+    let err = (-4000..=4000).map(|i| {  let x = i as f64 / 500.;
+        let (s, c) = approx::sin_cos(I16F16::from_num(x));
+        let x = I16F16::from_num(x).to_num::<f64>();
+        (s.to_num::<f64>() - x.sin()).abs().max((c.to_num::<f64>() - x.cos()).abs())
+    }).fold(0f64, f64::max);    assert!(err < 3.1e-5, "{err}");
+``` */
+#[cfg(feature = "fixed")] pub fn sin_cos(x: I16F16) -> (I16F16, I16F16) {
+    //  phase of 2^32 per turn, in wrapping arithmetic: x * 2^16 / 2π
+    let x = x.to_bits() as i64;
+    let phase = (x * 10430 + ((x * 1625002897) >> 32)) as u32;
+
+    let lookup = |p: u32| {     // p in [0, 2^30]
+        let (i, f) = ((p >> 22) as usize, (p & 0x3F_FFFF) as i64);
+        let (a, b) = (SIN_LUT[i] as i64, SIN_LUT[i + 1] as i64);
+        (a + (((b - a) * f + (1 << 21)) >> 22)) as i32
+    };
+
+    let p = phase & 0x3FFF_FFFF;
+    let (s, c) = (lookup(p), lookup((1 << 30) - p));
+    let (s, c) = match phase >> 30 { 0 => (s, c), 1 => (c, -s), 2 => (-s, -c), _ => (-c, s) };
+    (I16F16::from_bits(s), I16F16::from_bits(c))
+}
+
+#[cfg(feature = "fixed")] #[inline] pub fn sin(x: I16F16) -> I16F16 { sin_cos(x).0 }
+#[cfg(feature = "fixed")] #[inline] pub fn cos(x: I16F16) -> I16F16 { sin_cos(x).1 }
+
+/** Four-quadrant arctangent by CORDIC in vectoring mode with 30 iterations,
+    the absolute error is less than 2^-16 (about 1.5e-5) radians.
+```
+    use ugl_rs::math::{approx, I16F16};
+    let err = (-60..=60).flat_map(|y| (-60..=60).map(move |x| (y, x))).map(|(y, x)| {
+        let (y, x) = (y as f64 / 7., x as f64 / 3.);
+        let a = approx::atan2(I16F16::from_num(y), I16F16::from_num(x)).to_num::<f64>();
+        let (y, x) = (I16F16::from_num(y).to_num::<f64>(), I16F16::from_num(x).to_num::<f64>());
+        (a - y.atan2(x)).abs()
+    }).fold(0f64, f64::max);    assert!(err < 1.5e-5, "{err}");
+    assert_eq!(approx::atan2(I16F16::ZERO, I16F16::ZERO), 0);
+``` */
+#[cfg(feature = "fixed")] pub fn atan2(y: I16F16, x: I16F16) -> I16F16 {
+    I16F16::from_bits(atan2_bits(y.to_bits() as _, x.to_bits() as _))
+}
+
+/// The same as `atan2` but on arbitrary scaled integers, result in Q16.16.
+#[cfg(feature = "fixed")] pub(crate) fn atan2_bits(y: i64, x: i64) -> i32 {
+    if x == 0 && y == 0 { return 0 }
+    let (mut x, mut y, mut z) = if x < 0 {  // rotate to the right half plane
+        (-x, -y, if y < 0 { -PI_Q30 } else { PI_Q30 }) } else { (x, y, 0) };
+
+    //  normalize magnitudes into [2^29, 2^30) for precision and no overflow
+    let shift = (x.max(y.abs()) as u64).leading_zeros() as i32 - 34;
+    if 0 < shift { x <<= shift; y <<= shift } else { x >>= -shift; y >>= -shift }
+
+    for (i, &a) in ATAN_LUT.iter().enumerate() {
+        let (dx, dy) = (y >> i, x >> i);
+        if 0 < y { x += dx; y -= dy; z += a } else { x -= dx; y += dy; z -= a }
+    }   ((z + (1 << 13)) >> 14) as _
+}
+
+/** Square root by integer square root, truncated, the absolute error is less than 2^-16.
+    It returns 0 for negative numbers.
+```
+    use ugl_rs::math::{approx, I16F16};
+    let err = (0..20000).map(|i| {  let x = I16F16::from_bits(i * 107_351);
+        (approx::sqrt(x).to_num::<f64>() - x.to_num::<f64>().sqrt()).abs()
+    }).fold(0f64, f64::max);    assert!(err < 1.53e-5, "{err}");
+``` */
+#[cfg(feature = "fixed")] pub fn sqrt(x: I16F16) -> I16F16 {
+    if x <= 0 { return I16F16::ZERO }
+    I16F16::from_bits((((x.to_bits() as u64) << 16).isqrt()) as _)
+}
+
+/** Inverse square root by Newton-Raphson iterations (4 times) from a linear guess,
+    the relative error is less than 2^-15 (about 3.1e-5) besides the rounding of Q16.16.
+    It saturates to MAX for non-positive numbers.
+```
+    use ugl_rs::math::{approx, I16F16};
+    let err = (1..20000).map(|i| {  let x = I16F16::from_bits(i * 107_351);
+        let (v, e) = (approx::rsqrt(x).to_num::<f64>(), 1. / x.to_num::<f64>().sqrt());
+        (v - e).abs() / e - 0.5 / 65536. / e
+    }).fold(0f64, f64::max);    assert!(err < 3.1e-5, "{err}");
+    assert_eq!(approx::rsqrt(I16F16::from_num(4)), 0.5);
+``` */
+#[cfg(feature = "fixed")] pub fn rsqrt(x: I16F16) -> I16F16 {
+    let x = x.to_bits();    if x <= 0 { return I16F16::MAX }
+
+    //  normalize by an even shift, so that m in [1, 4) in Q2.30
+    let s = 30 - (31 - x.leading_zeros() as i32);   let s = s + (s & 1);
+    let m = (x as i64) << s;
+
+    let mut y = (1 << 30) - (((m - (1 << 30)) * 178956971) >> 30);  // chord: 1 - (m - 1) / 6
+    for _ in 0..4 { let yy = (y * y) >> 30; y = (y * ((3 << 30) - ((m * yy) >> 30))) >> 31; }
+
+    let sh = 14 - (s - 14) / 2;     I16F16::from_bits(((y + (1 << (sh - 1))) >> sh) as _)
+}
+
+/** Reciprocal by Newton-Raphson iterations (3 times) from the linear guess 48/17 - 32/17 x
+    (of x normalized in [0.5, 1)),
+    the relative error is less than 2^-20 besides the rounding of Q16.16.
+    It saturates for zero and too small numbers.
+```
+    use ugl_rs::math::{approx, I16F16};
+    let err = (-20000..20000).filter(|&i| i != 0).map(|i| {
+        let x = I16F16::from_bits(i * 107_351);
+        let (v, e) = (approx::recip(x).to_num::<f64>(), 1. / x.to_num::<f64>());
+        ((v - e).abs() - 0.5 / 65536.) / e.abs()
+    }).fold(0f64, f64::max);    assert!(err < 1. / (1 << 20) as f64, "{err}");
+    assert_eq!(approx::recip(I16F16::from_num(-8)), -0.125);
+    assert_eq!(approx::recip(I16F16::ZERO), I16F16::MAX);
+``` */
+#[cfg(feature = "fixed")] pub fn recip(x: I16F16) -> I16F16 {
+    let x = x.to_bits() as i64;     if x == 0 { return I16F16::MAX }
+    let a = x.abs();
+
+    //  normalize so that m in [0.5, 1) in Q2.30
+    let p = 63 - a.leading_zeros() as i64;  let m = (a << (31 - p)) >> 2;
+
+    let mut y = 3031741621 - ((2021161080 * m) >> 30);
+    for _ in 0..3 { y = (y * ((2 << 30) - ((m * y) >> 30))) >> 30; }
+
+    let v = if p <= 1 { y << (1 - p) } else { (y + (1 << (p - 2))) >> (p - 1) };
+    saturate(if x < 0 { -v } else { v })
+}
+
+/** Base-2 exponential by polynomial (degree 5) of the fractional part,
+    the relative error is less than 2^-20 besides the rounding of Q16.16.
+    It saturates to MAX for overflow.
+```
+    use ugl_rs::math::{approx, I16F16};
+    let err = (-16 * 256..15 * 256).map(|i| {  let x = I16F16::from_num(i as f64 / 256.);
+        let (v, e) = (approx::exp2(x).to_num::<f64>(), x.to_num::<f64>().exp2());
+        ((v - e).abs() - 0.5 / 65536.) / e
+    }).fold(0f64, f64::max);    assert!(err < 1. / (1 << 20) as f64, "{err}");
+    assert_eq!(approx::exp2(I16F16::from_num(3)), 8);
+``` */
+#[cfg(feature = "fixed")] pub fn exp2(x: I16F16) -> I16F16 {
+    const COEF: [i64; 6] = [1073741824, 744269106, 257849189, 59982742, 9605926, 2034856];
+    let x = x.to_bits();    let (n, f) = (x >> 16, ((x & 0xFFFF) as i64) << 14);
+    if 15 <= n { return I16F16::MAX }   let sh = 14 - n;
+    if 62 <= sh { return I16F16::ZERO }
+
+    let p = poly_q30(&COEF, f);     I16F16::from_bits(((p + ((1 << sh) >> 1)) >> sh) as _)
+}
+
+/** Base-2 logarithm by polynomial (degree 7) of the normalized mantissa,
+    the absolute error is less than 2^-16 (about 1.5e-5).
+    It saturates to MIN for non-positive numbers.
+```
+    use ugl_rs::math::{approx, I16F16};
+    let err = (1..20000).map(|i| {  let x = I16F16::from_bits(i * 107_351);
+        (approx::log2(x).to_num::<f64>() - x.to_num::<f64>().log2()).abs()
+    }).fold(0f64, f64::max);    assert!(err < 1.5e-5, "{err}");
+    assert_eq!(approx::log2(I16F16::from_num(1024)), 10);
+``` */
+#[cfg(feature = "fixed")] pub fn log2(x: I16F16) -> I16F16 {
+    const COEF: [i64; 8] = [0, 1549035902, -773509117, 507341813, -346941162, 204460019,
+        -82513686, 15867963];
+    let x = x.to_bits();    if x <= 0 { return I16F16::MIN }
+    let p = 31 - x.leading_zeros() as i32;
+    let t = ((x as i64) << (30 - p)) - (1 << 30);
+
+    let v = (((p - 16) as i64) << 16) + ((poly_q30(&COEF, t) + (1 << 13)) >> 14);
+    I16F16::from_bits(v as _)
+}
+
+//  http://www.machinedlearnings.com/2011/06/fast-approximate-logarithm-exponential.html
+//  https://github.com/romeric/fastapprox
+
+/** Fast base-2 logarithm of positive float, the absolute error is less than 2e-4.
+```
+    use ugl_rs::math::approx;
+    let err = (1..100000).map(|i| {     let x = i as f32 / 1000.;
+        (approx::log2f(x) - x.log2()).abs()
+    }).fold(0f32, f32::max);    assert!(err < 2e-4, "{err}");
+``` */
+#[inline] pub fn log2f(x: f32) -> f32 {
+    let vx = x.to_bits();
+    let mx = f32::from_bits((vx & 0x007F_FFFF) | 0x3F00_0000);
+    vx as f32 * 1.192_092_9e-7 - 124.225_52 - 1.498_030_3 * mx - 1.725_88 / (0.352_088_7 + mx)
+}
+
+/** Fast base-2 exponential of float, the relative error is less than 1e-4
+    (for results in the normal range).
+```
+    use ugl_rs::math::approx;
+    let err = (-12000..12000).map(|i| {     let x = i as f32 / 100.;
+        (approx::exp2f(x) / x.exp2() - 1.).abs()
+    }).fold(0f32, f32::max);    assert!(err < 1e-4, "{err}");
+``` */
+#[inline] pub fn exp2f(p: f32) -> f32 {
+    let offset = if p < 0. { 1. } else { 0. };
+    let clipp = p.max(-126.);   let z = clipp - (clipp as i32) as f32 + offset;
+    f32::from_bits(((1 << 23) as f32 *
+        (clipp + 121.274_06 + 27.728_024 / (4.842_525_7 - z) - 1.490_129_1 * z)) as u32)
+}
+
+/** Fast power function of positive base by `exp2f(log2f(x) * p)`,
+    the relative error is less than 5e-4 for results in [1e-6, 1e6].
+```
+    use ugl_rs::math::approx;
+    let err = (1..1000).map(|i| {   let x = i as f32 / 1000.;
+        (approx::powf(x, 2.2) / x.powf(2.2) - 1.).abs().max(
+        (approx::powf(x, 1. / 2.2) / x.powf(1. / 2.2) - 1.).abs())
+    }).fold(0f32, f32::max);    assert!(err < 5e-4, "{err}");
+``` */
+#[inline] pub fn powf(x: f32, p: f32) -> f32 { exp2f(log2f(x) * p) }
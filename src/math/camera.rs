@@ -0,0 +1,195 @@
+
+//  Camera controllers for interactive 3D viewers, in the conventions of `projection`:
+//  right-handed with y-axis up, the camera looks at -z direction in view space.
+
+use crate::math::{Scalar, quaternion::Quat, projection::*};
+
+/// Camera to produce the view matrix for the 3D pipeline.
+pub trait Camera<T: Scalar + 'static> {
+    /// position of the camera in world space
+    fn eye (&self) -> TVec3<T>;
+    /// the world-to-view matrix
+    fn view(&self) -> TMat4<T>;
+}
+
+#[inline] fn y_axis<T: Scalar + 'static>() -> TVec3<T> { TVec3::new(T::ZERO, T::ONE, T::ZERO) }
+
+//  keep the pitch away from the poles, where the up direction is ambiguous
+#[inline] fn clamp_pitch<T: Scalar>(pitch: T) -> T {
+    let limit = T::from_f32(core::f32::consts::FRAC_PI_2 - 0.001);  pitch.clamp(-limit, limit)
+}
+
+/** Camera orbiting around the target, by yaw around y-axis and pitch above the xz-plane,
+    zooming by the distance and panning the target in the view plane.
+```
+    use ugl_rs::math::{camera::*, projection::*};
+    let mut cam = OrbitCamera::new(TVec3::new(1., 2., 3.), 5.);
+    assert!((cam.eye() - TVec3::new(1., 2., 8.)).norm() < 1e-6);
+    cam.rotate(core::f32::consts::FRAC_PI_2, 0.3);    cam.zoom(0.5);
+    let pos = mul_vec4(&cam.view(), &TVec4::new(1., 2., 3., 1.));
+    assert!((pos - TVec4::new(0., 0., -2.5, 1.)).norm() < 1e-5);
+    assert!(cam.eye().x > 1. && cam.eye().y > 2.);
+    cam.pan(1., 0.);    assert!((cam.target.z - 2.).abs() < 1e-6);
+
+    #[cfg(feature = "fixed")] {     use ugl_rs::math::{I16F16, Scalar};
+        let mut fc = OrbitCamera::new(TVec3::new(1., 2., 3.).map(I16F16::from_f32),
+            I16F16::from_f32(5.));
+        fc.rotate(I16F16::from_f32(core::f32::consts::FRAC_PI_2), I16F16::from_f32(0.3));
+        fc.zoom(I16F16::HALF);  fc.pan(I16F16::ONE, I16F16::ZERO);
+        let err = (fc.view().map(|v| v.to_f32()) - cam.view()).abs().max();
+        assert!(err < 1e-3, "{err}");
+    }
+``` */
+#[derive(Clone, Copy, Debug, PartialEq)] pub struct OrbitCamera<T: Scalar + 'static = f32> {
+    pub target: TVec3<T>, pub distance: T,
+    /// angles in radians
+    pub yaw: T, pub pitch: T,
+}
+
+impl<T: Scalar + 'static> OrbitCamera<T> {
+    /// Looking at the target from the +z direction.
+    pub fn new(target: TVec3<T>, distance: T) -> Self {
+        Self { target, distance, yaw: T::ZERO, pitch: T::ZERO }
+    }
+
+    pub fn rotate(&mut self, dyaw: T, dpitch: T) {
+        self.yaw += dyaw;   self.pitch = clamp_pitch(self.pitch + dpitch);
+    }
+    /// Scale the distance to the target by the factor.
+    pub fn zoom(&mut self, factor: T) { self.distance *= factor }
+    /// Move the target (with the camera) along right and up directions of the view.
+    pub fn pan(&mut self, dx: T, dy: T) {
+        let (right, up) = self.axes();
+        self.target = add3(&self.target, &add3(&scale3(&right, dx), &scale3(&up, dy)));
+    }
+
+    fn offset(&self) -> TVec3<T> {     // unit vector from the target to the eye
+        let ((sy, cy), (sp, cp)) = (self.yaw.sin_cos(), self.pitch.sin_cos());
+        TVec3::new(cp * sy, sp, cp * cy)
+    }
+    fn axes(&self) -> (TVec3<T>, TVec3<T>) {
+        let back = self.offset();
+        let right = normalize3(&cross3(&y_axis(), &back));  (right, cross3(&back, &right))
+    }
+}
+
+impl<T: Scalar + 'static> Camera<T> for OrbitCamera<T> {
+    fn eye (&self) -> TVec3<T> { add3(&self.target, &scale3(&self.offset(), self.distance)) }
+    fn view(&self) -> TMat4<T> { look_at(&self.eye(), &self.target, &y_axis()) }
+}
+
+/** First-person camera at the position, looking by yaw (turning left if positive)
+    and pitch (looking up if positive), moving along its own directions.
+```
+    use ugl_rs::math::{camera::*, projection::*};
+    let mut cam = FirstPersonCamera::new(TVec3::new(0., 1., 0.));
+    assert!((cam.forward() - TVec3::new(0., 0., -1.)).norm() < 1e-6);
+    cam.look(core::f32::consts::FRAC_PI_2, 0.);
+    assert!((cam.forward() - TVec3::new(-1., 0., 0.)).norm() < 1e-6);
+
+    cam.walk(2., 1., 0.5);
+    assert!((cam.eye() - TVec3::new(-2., 1.5, -1.)).norm() < 1e-6);
+    let pos = mul_vec4(&cam.view(), &TVec4::new(-5., 1.5, -1., 1.));
+    assert!((pos - TVec4::new(0., 0., -3., 1.)).norm() < 1e-5);
+``` */
+#[derive(Clone, Copy, Debug, PartialEq)] pub struct FirstPersonCamera<T: Scalar + 'static = f32> {
+    pub position: TVec3<T>,
+    /// angles in radians
+    pub yaw: T, pub pitch: T,
+}
+
+impl<T: Scalar + 'static> FirstPersonCamera<T> {
+    /// Looking at -z direction.
+    pub fn new(position: TVec3<T>) -> Self { Self { position, yaw: T::ZERO, pitch: T::ZERO } }
+
+    pub fn look(&mut self, dyaw: T, dpitch: T) {
+        self.yaw += dyaw;   self.pitch = clamp_pitch(self.pitch + dpitch);
+    }
+
+    pub fn forward(&self) -> TVec3<T> {
+        let ((sy, cy), (sp, cp)) = (self.yaw.sin_cos(), self.pitch.sin_cos());
+        TVec3::new(-cp * sy, sp, -cp * cy)
+    }
+    /// Horizontal right direction.
+    pub fn right(&self) -> TVec3<T> {
+        let (sy, cy) = self.yaw.sin_cos();  TVec3::new(cy, T::ZERO, -sy)
+    }
+
+    /// Move along the forward, (horizontal) right and world up directions.
+    pub fn walk(&mut self, forward: T, right: T, up: T) {
+        let delta = add3(&scale3(&self.forward(), forward), &scale3(&self.right(), right));
+        self.position = add3(&self.position, &add3(&delta, &scale3(&y_axis(), up)));
+    }
+}
+
+impl<T: Scalar + 'static> Camera<T> for FirstPersonCamera<T> {
+    fn eye (&self) -> TVec3<T> { self.position }
+    fn view(&self) -> TMat4<T> {
+        look_at(&self.position, &add3(&self.position, &self.forward()), &y_axis())
+    }
+}
+
+/** Virtual trackball camera around the target, rotating freely (no fixed up direction)
+    by dragging on the screen, refer to https://www.khronos.org/opengl/wiki/Object_Mouse_Trackball
+```
+    use ugl_rs::math::{camera::*, projection::*};
+    let mut cam = TrackballCamera::new(TVec3::new(0., 0., 0f32), 4.);
+    assert!((cam.eye() - TVec3::new(0., 0., 4.)).norm() < 1e-6);
+
+    cam.drag((0., 0.), (0.5, 0.));      // the object rotates right, the camera orbits left
+    assert!(cam.eye().x < 0. && (cam.eye().norm() - 4.).abs() < 1e-5);
+    let pos = mul_vec4(&cam.view(), &TVec4::new(0., 0., 0., 1.));
+    assert!((pos - TVec4::new(0., 0., -4., 1.)).norm() < 1e-5);
+
+    let eye = cam.eye();    cam.drag((0.5, 0.), (0., 0.));
+    assert!((cam.eye() - TVec3::new(0., 0., 4.)).norm() < 1e-5 && eye != cam.eye());
+``` */
+#[derive(Clone, Copy, Debug, PartialEq)] pub struct TrackballCamera<T: Scalar + 'static = f32> {
+    pub target: TVec3<T>, pub distance: T,
+    /// orientation of the camera (view to world)
+    pub rotation: Quat<T>,
+}
+
+impl<T: Scalar + 'static> TrackballCamera<T> {
+    /// Looking at the target from the +z direction.
+    pub fn new(target: TVec3<T>, distance: T) -> Self {
+        Self { target, distance, rotation: Quat::identity() }
+    }
+
+    /// Project the point in normalized screen coordinates onto the sphere
+    /// (of radius 1) blended with the hyperbolic sheet.
+    fn project(x: T, y: T) -> TVec3<T> {
+        let d2 = x * x + y * y;
+        let z = if d2 <= T::HALF { (T::ONE - d2).sqrt() } else { T::HALF / d2.sqrt() };
+        normalize3(&TVec3::new(x, y, z))
+    }
+
+    /// Rotate by dragging from one point to another, in normalized screen coordinates
+    /// ([-1, 1] with y-axis up).
+    pub fn drag(&mut self, from: (T, T), to: (T, T)) {
+        let (p0, p1) = (Self::project(from.0, from.1), Self::project(to.0, to.1));
+        let axis = cross3(&p0, &p1);
+        let spin = Quat::new(axis.x, axis.y, axis.z, T::ONE + dot3(&p0, &p1));
+        if spin.length() <= T::EPSILON { return }
+        //  object rotates by `spin` in the view space, i.e. the camera by the inverse
+        self.rotation = (self.rotation * spin.normalize().conjugate()).normalize();
+    }
+
+    pub fn zoom(&mut self, factor: T) { self.distance *= factor }
+}
+
+impl<T: Scalar + 'static> Camera<T> for TrackballCamera<T> {
+    fn eye (&self) -> TVec3<T> {
+        let back = self.rotation.rotate(&TVec3::new(T::ZERO, T::ZERO, T::ONE));
+        add3(&self.target, &scale3(&back, self.distance))
+    }
+
+    fn view(&self) -> TMat4<T> {
+        let (r, eye) = (self.rotation.to_mat3(), self.eye());
+        TMat4::from_fn(|i, j| match (i, j) {
+            (3, 3) => T::ONE, (3, _) => T::ZERO,
+            (_, 3) => -(r[(0, i)] * eye.x + r[(1, i)] * eye.y + r[(2, i)] * eye.z),
+            _ => r[(j, i)],     // transpose of the orientation
+        })
+    }
+}
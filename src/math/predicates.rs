@@ -0,0 +1,172 @@
+
+//  https://www.cs.cmu.edu/~quake/robust.html
+//  Adaptive precision floating-point arithmetic and fast robust geometric predicates,
+//  Jonathan Richard Shewchuk, 1997. All `Scalar` types (f32/f64, Q16.16/Q24.8) are
+//  converted to f64 exactly, evaluated by a fast filter and fallback to the exact
+//  arithmetic of expansions (ignoring overflow/underflow).
+
+use crate::{geometry::Point, math::Scalar};
+
+const EPS: f64 = f64::EPSILON / 2.;     // half ulp of 1.0
+
+#[inline] fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;  let bv = x - a;     let av = x - bv;    (x, (a - av) + (b - bv))
+}
+#[inline] fn two_product(a: f64, b: f64) -> (f64, f64) { let x = a * b; (x, a.mul_add(b, -x)) }
+
+/// Expansion: sum of non-overlapping components in increasing magnitude, without zeros.
+type Expansion = Vec<f64>;
+
+fn grow(e: &[f64], b: f64) -> Expansion {
+    let mut q = b;
+    let mut h = e.iter().filter_map(|&v| {
+        let (s, t) = two_sum(q, v);     q = s;  (t != 0.).then_some(t)
+    }).collect::<Expansion>();  if q != 0. { h.push(q) }    h
+}
+
+fn add(e: &[f64], f: &[f64]) -> Expansion { f.iter().fold(e.to_vec(), |h, &v| grow(&h, v)) }
+fn sub(e: &[f64], f: &[f64]) -> Expansion { f.iter().fold(e.to_vec(), |h, &v| grow(&h, -v)) }
+fn mul(e: &[f64], f: &[f64]) -> Expansion {
+    e.iter().flat_map(|&a| f.iter().map(move |&b| two_product(a, b)))
+        .fold(vec![], |h, (x, y)| grow(&grow(&h, y), x))
+}
+#[inline] fn diff(a: f64, b: f64) -> Expansion { grow(&[a], -b) }
+/// The most significant component has the sign of the expansion, so is the approximation.
+#[inline] fn estimate(e: &[f64]) -> f64 { e.iter().sum() }
+
+#[inline] fn to_f64<T: Scalar>(pt: Point<T>) -> (f64, f64) { (pt.x.to_f64(), pt.y.to_f64()) }
+
+/** Orientation of the three points with exact sign: positive if they are in
+    counter-clockwise order (in y-up coordinates, i.e. clockwise in y-down),
+    negative if clockwise, and zero if collinear. The magnitude approximates
+    twice the signed area of the triangle.
+```
+    use ugl_rs::{geometry::Point, math::predicates::orient2d};
+    let (a, b) = (Point::new(12f64, 12.), Point::new(24., 24.));
+    let c = Point::new(0.5 + f64::EPSILON, 0.5);    // slightly right of the line
+    let naive = (a.x - c.x) * (b.y - c.y) - (a.y - c.y) * (b.x - c.x);
+    assert!(0. <= naive && orient2d(a, b, c) < 0.);
+    assert_eq!(orient2d(a, b, Point::new(0.5, 0.5)), 0.);
+    assert!(orient2d(Point::new(0f32, 0.), Point::new(1., 0.), Point::new(0., 1.)) > 0.);
+
+    #[cfg(feature = "fixed")] {     use ugl_rs::math::I16F16;
+        let p = |x: f32, y: f32| Point::new(I16F16::from_num(x), I16F16::from_num(y));
+        assert_eq!(orient2d(p(-30000., -0.5), p(0., 0.), p(30000., 0.5)), 0.);
+        assert!(orient2d(p(-30000., -0.5), p(0., 0.), p(30000., 0.50002)) > 0.);
+    }
+``` */
+pub fn orient2d<T: Scalar>(a: Point<T>, b: Point<T>, c: Point<T>) -> f64 {
+    let ((ax, ay), (bx, by), (cx, cy)) = (to_f64(a), to_f64(b), to_f64(c));
+    let (left, right) = ((ax - cx) * (by - cy), (ay - cy) * (bx - cx));
+    let det = left - right;
+    if (3. + 16. * EPS) * EPS * (left.abs() + right.abs()) < det.abs() { return det }
+
+    estimate(&sub(&mul(&diff(ax, cx), &diff(by, cy)), &mul(&diff(ay, cy), &diff(bx, cx))))
+}
+
+/** Whether `d` is inside the circle through `a`, `b` and `c` with exact sign:
+    positive if inside while `a`, `b`, `c` are in counter-clockwise order (see `orient2d`),
+    negative if outside, and zero if cocircular; the sign is reversed if they're clockwise.
+```
+    use ugl_rs::{geometry::Point, math::predicates::incircle};
+    let (a, b, c) = (Point::new(0f64, 0.), Point::new(1., 0.), Point::new(0., 1.));
+    assert_eq!(incircle(a, b, c, Point::new(1., 1.)), 0.);
+    assert!(incircle(a, b, c, Point::new(0.5, 0.5)) > 0.);
+    assert!(incircle(a, b, c, Point::new(1., 1. + f64::EPSILON)) < 0.);
+    assert!(incircle(a, c, b, Point::new(0.5, 0.5)) < 0.);
+``` */
+pub fn incircle<T: Scalar>(a: Point<T>, b: Point<T>, c: Point<T>, d: Point<T>) -> f64 {
+    let ((ax, ay), (bx, by), (cx, cy), (dx, dy)) = (to_f64(a), to_f64(b), to_f64(c), to_f64(d));
+    let (adx, ady, bdx, bdy, cdx, cdy) = (ax - dx, ay - dy, bx - dx, by - dy, cx - dx, cy - dy);
+
+    let (bdxcdy, cdxbdy) = (bdx * cdy, cdx * bdy);  let alift = adx * adx + ady * ady;
+    let (cdxady, adxcdy) = (cdx * ady, adx * cdy);  let blift = bdx * bdx + bdy * bdy;
+    let (adxbdy, bdxady) = (adx * bdy, bdx * ady);  let clift = cdx * cdx + cdy * cdy;
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift +
+        (cdxady.abs() + adxcdy.abs()) * blift + (adxbdy.abs() + bdxady.abs()) * clift;
+    if (10. + 96. * EPS) * EPS * permanent < det.abs() { return det }
+
+    let (adx, ady, bdx, bdy, cdx, cdy) =
+        (diff(ax, dx), diff(ay, dy), diff(bx, dx), diff(by, dy), diff(cx, dx), diff(cy, dy));
+    let lift = |x: &[f64], y: &[f64]| add(&mul(x, x), &mul(y, y));
+    let cross = |x0: &[f64], y1: &[f64], x1: &[f64], y0: &[f64]|
+        sub(&mul(x0, y1), &mul(x1, y0));
+
+    estimate(&add(&add(&mul(&lift(&adx, &ady), &cross(&bdx, &cdy, &cdx, &bdy)),
+                       &mul(&lift(&bdx, &bdy), &cross(&cdx, &ady, &adx, &cdy))),
+                       &mul(&lift(&cdx, &cdy), &cross(&adx, &bdy, &bdx, &ady))))
+}
+
+/// Intersection of two line segments, classified by exact predicates.
+#[derive(Clone, Copy, Debug, PartialEq)] pub enum SegmentIntersection<T: Scalar = f32> {
+    None,
+    /// crossing at the interior of both segments, the point is rounded
+    Cross(Point<T>),
+    /// touching at an endpoint of either segment, the point is exact
+    Touch(Point<T>),
+    /// collinear and overlapped, the endpoints are exact and in the direction of the first
+    Overlap(Point<T>, Point<T>),
+}
+
+/** Intersect segment `a0 a1` with segment `b0 b1`, degenerate segments (points) are allowed.
+```
+    use ugl_rs::{geometry::Point, math::predicates::*};
+    let p = |x, y| Point::new(x, y);
+    assert_eq!(segment_intersection(p(0., 0.), p(2., 2.), p(0., 2.), p(2., 0.)),
+        SegmentIntersection::Cross(p(1., 1.)));
+    assert_eq!(segment_intersection(p(0., 0.), p(2., 2.), p(1., 1.), p(2., 0.)),
+        SegmentIntersection::Touch(p(1., 1.)));
+    assert_eq!(segment_intersection(p(0., 0.), p(2., 2.), p(3., 3.), p(1., 1.)),
+        SegmentIntersection::Overlap(p(1., 1.), p(2., 2.)));
+    assert_eq!(segment_intersection(p(0., 0.), p(2., 2.), p(2., 2.), p(3., 3.)),
+        SegmentIntersection::Touch(p(2., 2.)));
+    assert_eq!(segment_intersection(p(0., 0.), p(2., 2.), p(0., 1.), p(2., 3.)),
+        SegmentIntersection::None);
+    assert_eq!(segment_intersection(p(1., 1.), p(1., 1.), p(0., 0.), p(0., 2.)),
+        SegmentIntersection::None);
+
+    #[cfg(feature = "fixed")] {     use ugl_rs::math::I16F16;
+        let p = |x: f32, y: f32| Point::new(I16F16::from_num(x), I16F16::from_num(y));
+        assert_eq!(segment_intersection(p(-1., 0.), p(3., 0.), p(0., -1.), p(0., 3.)),
+            SegmentIntersection::Cross(p(0., 0.)));
+    }
+``` */
+pub fn segment_intersection<T: Scalar>(a0: Point<T>, a1: Point<T>,
+    b0: Point<T>, b1: Point<T>) -> SegmentIntersection<T> {
+    let (d0, d1) = (orient2d(a0, a1, b0), orient2d(a0, a1, b1));
+    let (d2, d3) = (orient2d(b0, b1, a0), orient2d(b0, b1, a1));
+    let apart = |u: f64, v: f64| (0. < u && 0. < v) || (u < 0. && v < 0.);
+    if apart(d0, d1) || apart(d2, d3) { return SegmentIntersection::None }
+
+    if d0 == 0. && d1 == 0. && d2 == 0. && d3 == 0. {   // collinear, or degenerate
+        let pts = [a0, a1, b0, b1];
+        let range = |key: fn(&Point<T>) -> T| {
+            let (min, max) = pts.iter().map(key).fold((T::MAX, T::MIN),
+                |(min, max), v| (min.min(v), max.max(v)));  max - min
+        };
+        let key: fn(&Point<T>) -> T = if range(|p| p.y) <= range(|p| p.x) {
+            |p| p.x } else { |p| p.y };
+
+        let (mut lo_a, mut hi_a) = (a0, a1);
+        let reversed = key(&a1) < key(&a0);     if reversed { (lo_a, hi_a) = (a1, a0) }
+        let (lo_b, hi_b) = if key(&b1) < key(&b0) { (b1, b0) } else { (b0, b1) };
+
+        let lo = if key(&lo_a) < key(&lo_b) { lo_b } else { lo_a };
+        let hi = if key(&hi_b) < key(&hi_a) { hi_b } else { hi_a };
+        return if key(&hi) < key(&lo) { SegmentIntersection::None }
+            else if key(&hi) == key(&lo) { SegmentIntersection::Touch(lo) }
+            else if reversed { SegmentIntersection::Overlap(hi, lo) }
+            else { SegmentIntersection::Overlap(lo, hi) }
+    }
+
+    if d0 == 0. { return SegmentIntersection::Touch(b0) }
+    if d1 == 0. { return SegmentIntersection::Touch(b1) }
+    if d2 == 0. { return SegmentIntersection::Touch(a0) }
+    if d3 == 0. { return SegmentIntersection::Touch(a1) }
+
+    let ((x0, y0), (x1, y1)) = (to_f64(a0), to_f64(a1));
+    let t = d2 / (d2 - d3);
+    SegmentIntersection::Cross(Point::new(T::from_f64(x0 + (x1 - x0) * t),
+        T::from_f64(y0 + (y1 - y0) * t)))
+}
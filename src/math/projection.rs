@@ -0,0 +1,188 @@
+
+//  https://www.songho.ca/opengl/gl_projectionmatrix.html
+//  https://www.gamedevs.org/uploads/fast-extraction-viewing-frustum-planes-from-world-view-projection-matrix.pdf
+
+//  Projective 3D helpers generic over `Scalar` (so also for fixed-point), the same
+//  conventions as `nalgebra_glm` defaults: right-handed, clip space of z in [-1, 1].
+
+pub use nalgebra_glm::{TMat3, TMat4, TVec3, TVec4};
+use crate::math::Scalar;
+
+#[inline] pub(crate) fn add3<T: Scalar + 'static>(a: &TVec3<T>, b: &TVec3<T>) -> TVec3<T> {
+    TVec3::new(a.x + b.x, a.y + b.y, a.z + b.z)
+}
+#[inline] pub(crate) fn sub3<T: Scalar + 'static>(a: &TVec3<T>, b: &TVec3<T>) -> TVec3<T> {
+    TVec3::new(a.x - b.x, a.y - b.y, a.z - b.z)
+}
+#[inline] pub(crate) fn scale3<T: Scalar + 'static>(v: &TVec3<T>, s: T) -> TVec3<T> {
+    TVec3::new(v.x * s, v.y * s, v.z * s)
+}
+#[inline] pub(crate) fn dot3<T: Scalar + 'static>(a: &TVec3<T>, b: &TVec3<T>) -> T {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+#[inline] pub(crate) fn cross3<T: Scalar + 'static>(a: &TVec3<T>, b: &TVec3<T>) -> TVec3<T> {
+    TVec3::new(a.y * b.z - a.z * b.y, a.z * b.x - a.x * b.z, a.x * b.y - a.y * b.x)
+}
+#[inline] pub(crate) fn normalize3<T: Scalar + 'static>(v: &TVec3<T>) -> TVec3<T> {
+    let len = dot3(v, v).sqrt();    TVec3::new(v.x / len, v.y / len, v.z / len)
+}
+
+/** Perspective projection by the aspect ratio (width / height), vertical field of view
+    in radians and distances of near/far planes, as `nalgebra_glm::perspective`.
+```
+    use ugl_rs::math::projection::*;
+    use core::f32::consts::FRAC_PI_3;
+
+    let near = |a: &TMat4<f32>, b: &TMat4<f32>, eps| (a - b).abs().max() < eps;
+    let proj = perspective(4. / 3., FRAC_PI_3, 0.1, 100.);
+    assert!(near(&proj, &nalgebra_glm::perspective(4. / 3., FRAC_PI_3, 0.1, 100.), 1e-6));
+    assert!(near(&ortho(-2., 2., -1., 1., 0.1, 100.),
+        &nalgebra_glm::ortho(-2., 2., -1., 1., 0.1, 100.), 1e-6));
+
+    let (eye, center, up) = (TVec3::new(1., 2., 3.), TVec3::new(0., 0., 0.), TVec3::y());
+    let view = look_at(&eye, &center, &up);
+    assert!(near(&view, &nalgebra_glm::look_at(&eye, &center, &up), 1e-6));
+    assert!(near(&mul_mat4(&proj, &view), &(proj * view), 1e-5));
+
+    #[cfg(feature = "fixed")] {     use ugl_rs::math::{I16F16, Scalar};
+        let fx = perspective(I16F16::from_num(4. / 3.), I16F16::from_num(FRAC_PI_3),
+            I16F16::from_num(0.1), I16F16::from_num(100));
+        assert!(near(&fx.map(|v| v.to_f32()), &proj, 1e-3));
+    }
+``` */
+pub fn perspective<T: Scalar + 'static>(aspect: T, fovy: T, near: T, far: T) -> TMat4<T> {
+    let (sin, cos) = (fovy * T::HALF).sin_cos();
+    let (f, o, d) = (cos / sin, T::ZERO, near - far);
+    TMat4::new(f / aspect, o, o, o,   o, f, o, o,
+        o, o, (far + near) / d, T::TWO * far * near / d,   o, o, -T::ONE, o)
+}
+
+/// Orthographic projection by the clipping planes, as `nalgebra_glm::ortho`.
+pub fn ortho<T: Scalar + 'static>(left: T, right: T, bottom: T, top: T,
+    near: T, far: T) -> TMat4<T> {
+    let (w, h, d, o) = (right - left, top - bottom, far - near, T::ZERO);
+    TMat4::new(T::TWO / w, o, o, -(right + left) / w,   o, T::TWO / h, o, -(top + bottom) / h,
+        o, o, -T::TWO / d, -(far + near) / d,   o, o, o, T::ONE)
+}
+
+/// View matrix looking at `center` from `eye` with the `up` direction, as `nalgebra_glm::look_at`.
+pub fn look_at<T: Scalar + 'static>(eye: &TVec3<T>, center: &TVec3<T>,
+    up: &TVec3<T>) -> TMat4<T> {
+    let f = normalize3(&sub3(center, eye));
+    let s = normalize3(&cross3(&f, up));    let u = cross3(&s, &f);
+    let o = T::ZERO;
+    TMat4::new( s.x,  s.y,  s.z, -dot3(&s, eye),     u.x,  u.y,  u.z, -dot3(&u, eye),
+               -f.x, -f.y, -f.z,  dot3(&f, eye),       o,    o,    o,  T::ONE)
+}
+
+/// Matrix multiplication `a * b`, without requirement of numeric traits of `nalgebra`.
+pub fn mul_mat4<T: Scalar + 'static>(a: &TMat4<T>, b: &TMat4<T>) -> TMat4<T> {
+    TMat4::from_fn(|r, c| (0..4).fold(T::ZERO, |acc, k| acc + a[(r, k)] * b[(k, c)]))
+}
+
+/// Transform the homogeneous vector, `m * v`.
+pub fn mul_vec4<T: Scalar + 'static>(m: &TMat4<T>, v: &TVec4<T>) -> TVec4<T> {
+    TVec4::from_fn(|r, _| (0..4).fold(T::ZERO, |acc, k| acc + m[(r, k)] * v[k]))
+}
+
+/** Normal matrix (inverse transpose of the upper-left 3x3) of the model-view matrix,
+    for transforming normals, `None` if it's singular.
+```
+    use ugl_rs::math::projection::*;
+    let mv = nalgebra_glm::scale(&nalgebra_glm::rotation(0.5, &TVec3::z()), &TVec3::new(2., 1., 1f32));
+    let nm = normal_matrix(&mv).unwrap();
+    let (tangent, normal) = (TVec3::new(1., 1., 0.), TVec3::new(1., -1., 0.));
+    assert!((nm * normal).dot(&(mv.fixed_view::<3, 3>(0, 0) * tangent)).abs() < 1e-6);
+    assert_eq!(normal_matrix(&nalgebra_glm::scaling(&TVec3::new(0., 1., 1.))), None);
+``` */
+pub fn normal_matrix<T: Scalar + 'static>(m: &TMat4<T>) -> Option<TMat3<T>> {
+    let e = |r: usize, c: usize| m[(r % 3, c % 3)];
+    let cof = TMat3::from_fn(|r, c|     // cofactors by cyclic indices
+        e(r + 1, c + 1) * e(r + 2, c + 2) - e(r + 1, c + 2) * e(r + 2, c + 1));
+    let det = (0..3).fold(T::ZERO, |acc, c| acc + m[(0, c)] * cof[(0, c)]);
+    if det.abs() <= T::EPSILON { return None }
+    Some(cof.map(|v| v / det))
+}
+
+/** Frustum planes (normalized, pointing inwards) extracted from the projection
+    (or model-view-projection) matrix, in order of left, right, bottom, top, near and far,
+    a point `p` is inside if `plane.xyz · p + plane.w >= 0` for all planes.
+```
+    use ugl_rs::math::projection::*;
+    let planes = frustum_planes(&ortho(-2., 2., -1., 1., 1., 3f32));
+    assert_eq!(planes[0], TVec4::new(1., 0., 0., 2.));
+    assert_eq!(planes[4], TVec4::new(0., 0., -1., -1.));
+    assert_eq!(planes[5], TVec4::new(0., 0.,  1., 3.));
+``` */
+pub fn frustum_planes<T: Scalar + 'static>(m: &TMat4<T>) -> [TVec4<T>; 6] {
+    core::array::from_fn(|i| {  let (r, s) = (i / 2, if i % 2 == 0 { T::ONE } else { -T::ONE });
+        let p = TVec4::from_fn(|c, _| m[(3, c)] + s * m[(r, c)]);
+        let len = (p.x * p.x + p.y * p.y + p.z * p.z).sqrt();   p.map(|v| v / len)
+    })
+}
+
+/** Outcode of the homogeneous clip-space vertex against the view volume
+    (-w <= x, y, z <= w), bits in the same order as `frustum_planes`, 0 if inside.
+```
+    use ugl_rs::math::projection::*;
+    assert_eq!(clip_outcode(&TVec4::new(0.5, 0., 0., 1.)), 0);
+    assert_eq!(clip_outcode(&TVec4::new(2., -2., 0., 1.)), 0b0110);
+    assert_eq!(clip_outcode(&TVec4::new(0., 0., 2., 1.)), 0b100000);
+``` */
+pub fn clip_outcode<T: Scalar + 'static>(v: &TVec4<T>) -> u8 {
+    let w = v.w;    [v.x, v.y, v.z].iter().enumerate().fold(0, |code, (i, &c)| code |
+        ((c < -w) as u8) << (i * 2) | ((w < c) as u8) << (i * 2 + 1))
+}
+
+/// Number of fractional bits of the subpixel precision of screen coordinates.
+pub const SUBPIXEL_BITS: u32 = 8;
+
+/// Vertex mapped to screen, with coordinates in pixels of `SUBPIXEL_BITS` fractional bits.
+#[derive(Clone, Copy, Debug, PartialEq)] pub struct ScreenVertex<T: Scalar> {
+    pub x: i32, pub y: i32,
+    /// depth in [0, 1]
+    pub z: T,
+    /// reciprocal of clip-space w, for perspective-correct interpolation
+    pub w: T,
+}
+
+/** Viewport to map normalized device coordinates to the window (y-down as images).
+```
+    use ugl_rs::math::projection::*;
+    let vp = Viewport::new(0, 0, 640, 480);
+    let sv = vp.map(&TVec4::new(-1., 1., -1., 1f32)).unwrap();
+    assert_eq!((sv.x, sv.y, sv.z, sv.w), (0, 0, 0., 1.));
+    let sv = vp.map(&TVec4::new(0.5, 0., 2., 4f32)).unwrap();
+    assert_eq!((sv.x, sv.y, sv.z, sv.w), (360 << SUBPIXEL_BITS, 240 << SUBPIXEL_BITS, 0.75, 0.25));
+    assert_eq!(vp.map(&TVec4::new(0., 0., 0., -1f32)), None);
+
+    #[cfg(feature = "fixed")] {     use ugl_rs::math::{I16F16, Scalar};
+        let v = TVec4::new(0.3, -0.7, 0.5, 1.5);
+        let (sv, fv) = (vp.map(&v).unwrap(), vp.map(&v.map(I16F16::from_f32)).unwrap());
+        assert!((sv.x - fv.x).abs() <= 1 && (sv.y - fv.y).abs() <= 1);
+    }
+``` */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)] pub struct Viewport {
+    pub x: i32, pub y: i32, pub width: i32, pub height: i32,
+}
+
+impl Viewport {
+    #[inline] pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self { x, y, width, height }
+    }
+
+    /// Map the clip-space vertex to screen, `None` if it's behind the eye (w <= 0).
+    pub fn map<T: Scalar + 'static>(&self, v: &TVec4<T>) -> Option<ScreenVertex<T>> {
+        if v.w <= T::ZERO { return None }
+        let rw = v.w.recip();
+
+        //  split integer and fractional parts, to avoid overflow of fixed-point
+        let subpixel = |v: T, org: i32| ((v.floor().to_i32() + org) << SUBPIXEL_BITS) +
+            (v.fract() * T::from_i32(1 << SUBPIXEL_BITS)).round().to_i32();
+        let half = |ndc: T, size: i32| ndc * T::HALF * T::from_i32(size);
+        let (cx, cy) = (half(v.x * rw + T::ONE, self.width), half(T::ONE - v.y * rw, self.height));
+
+        Some(ScreenVertex { x: subpixel(cx, self.x), y: subpixel(cy, self.y),
+            z: (v.z * rw + T::ONE) * T::HALF, w: rw })
+    }
+}
@@ -0,0 +1,129 @@
+
+//  https://en.wikipedia.org/wiki/Quaternions_and_spatial_rotation
+//  https://www.euclideanspace.com/maths/geometry/rotations/conversions/
+
+use core::ops::Mul;
+use crate::math::{Scalar, projection::{cross3, dot3, TMat3, TMat4, TVec3}};
+
+/** Quaternion `w + xi + yj + zk` for 3D rotations, generic over `Scalar`,
+    (x, y, z, w) in the same order as `nalgebra_glm::Qua::coords`.
+```
+    use ugl_rs::math::{projection::*, quaternion::Quat};
+    use core::f32::consts::FRAC_PI_2;
+    let near = |a: &TMat4<f32>, b: &TMat4<f32>| (a - b).abs().max() < 1e-6;
+
+    let axis = TVec3::new(1., 2., 3.).normalize();
+    let q = Quat::from_axis_angle(&axis, 0.7);
+    let g = nalgebra_glm::quat_angle_axis(0.7, &axis);
+    assert!(near(&q.to_mat4(), &nalgebra_glm::quat_to_mat4(&g)));
+    let (axis2, angle) = q.to_axis_angle();
+    assert!((axis2 - axis).norm() < 1e-6 && (angle - 0.7).abs() < 1e-6);
+
+    let r = Quat::from_axis_angle(&TVec3::y(), FRAC_PI_2);
+    assert!((r.rotate(&TVec3::new(0., 0., -1.)) - TVec3::new(-1., 0., 0.)).norm() < 1e-6);
+    assert!(near(&(q * r).to_mat4(), &(q.to_mat4() * r.to_mat4())));
+    assert_eq!(Quat::from_euler(FRAC_PI_2, 0., 0.), r);
+
+    let h = q.slerp(&r, 0.3);
+    let gh = nalgebra_glm::quat_slerp(&g, &nalgebra_glm::quat_angle_axis(FRAC_PI_2, &TVec3::y()), 0.3);
+    assert!(near(&h.to_mat4(), &nalgebra_glm::quat_to_mat4(&gh)));
+    assert!((q.slerp(&r, 0.).dot(&q) - 1.).abs() < 1e-6);
+
+    #[cfg(feature = "fixed")] {     use ugl_rs::math::{I16F16, Scalar};
+        let axis = axis.map(I16F16::from_f32);
+        let qf = Quat::from_axis_angle(&axis, I16F16::from_f32(0.7));
+        let err = (qf.to_mat4().map(|v| v.to_f32()) - q.to_mat4()).abs().max();
+        assert!(err < 1e-3, "{err}");
+    }
+``` */
+#[derive(Clone, Copy, Debug, PartialEq)] pub struct Quat<T: Scalar = f32> {
+    pub x: T, pub y: T, pub z: T, pub w: T,
+}
+
+impl<T: Scalar + 'static> Default for Quat<T> { #[inline] fn default() -> Self { Self::identity() } }
+
+impl<T: Scalar + 'static> Quat<T> {
+    #[inline] pub fn new(x: T, y: T, z: T, w: T) -> Self { Self { x, y, z, w } }
+    #[inline] pub fn identity() -> Self { Self::new(T::ZERO, T::ZERO, T::ZERO, T::ONE) }
+
+    #[inline] fn vec(&self) -> TVec3<T> { TVec3::new(self.x, self.y, self.z) }
+
+    /// Rotation by the angle in radians around the (unit) axis, counter-clockwise
+    /// when looking from the positive side of the axis.
+    pub fn from_axis_angle(axis: &TVec3<T>, angle: T) -> Self {
+        let (sin, cos) = (angle * T::HALF).sin_cos();
+        Self::new(axis.x * sin, axis.y * sin, axis.z * sin, cos)
+    }
+
+    /// Rotation by Euler angles in radians: yaw around y-axis, pitch around x-axis
+    /// and roll around z-axis, applied in order of roll, pitch and yaw.
+    pub fn from_euler(yaw: T, pitch: T, roll: T) -> Self {
+        let (o, one) = (T::ZERO, T::ONE);
+        Self::from_axis_angle(&TVec3::new(o, one, o), yaw) *
+        Self::from_axis_angle(&TVec3::new(one, o, o), pitch) *
+        Self::from_axis_angle(&TVec3::new(o, o, one), roll)
+    }
+
+    /// The unit axis and the angle in radians (in [0, 2π]), x-axis for no rotation.
+    pub fn to_axis_angle(&self) -> (TVec3<T>, T) {
+        let v = self.vec();     let len = dot3(&v, &v).sqrt();
+        let angle = T::TWO * len.atan2(self.w);
+        if len <= T::EPSILON { return (TVec3::new(T::ONE, T::ZERO, T::ZERO), angle) }
+        (v.map(|c| c / len), angle)
+    }
+
+    #[inline] pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+    #[inline] pub fn length(&self) -> T { self.dot(self).sqrt() }
+    #[inline] pub fn normalize(&self) -> Self {
+        let len = self.length();    Self::new(self.x / len, self.y / len, self.z / len, self.w / len)
+    }
+    /// The inverse rotation for unit quaternion.
+    #[inline] pub fn conjugate(&self) -> Self { Self::new(-self.x, -self.y, -self.z, self.w) }
+
+    /// Rotate the vector by the unit quaternion.
+    pub fn rotate(&self, v: &TVec3<T>) -> TVec3<T> {
+        let u = self.vec();     let t = cross3(&u, v).map(|c| c * T::TWO);
+        let ut = cross3(&u, &t);
+        TVec3::new(v.x + self.w * t.x + ut.x, v.y + self.w * t.y + ut.y, v.z + self.w * t.z + ut.z)
+    }
+
+    /// Spherical linear interpolation along the shortest path,
+    /// fallback to normalized linear interpolation for nearly identical rotations.
+    pub fn slerp(&self, other: &Self, t: T) -> Self {
+        let (mut d, mut other) = (self.dot(other), *other);
+        if d < T::ZERO { d = -d; other = Self::new(-other.x, -other.y, -other.z, -other.w) }
+
+        let (a, b) = if T::from_f32(0.9995) < d { (T::ONE - t, t) } else {
+            let theta = (T::ONE - d * d).max(T::ZERO).sqrt().atan2(d);
+            let (s, _) = theta.sin_cos();
+            (((T::ONE - t) * theta).sin_cos().0 / s, (t * theta).sin_cos().0 / s)
+        };
+
+        Self::new(self.x * a + other.x * b, self.y * a + other.y * b,
+                  self.z * a + other.z * b, self.w * a + other.w * b).normalize()
+    }
+
+    /// Rotation matrix of the unit quaternion.
+    pub fn to_mat3(&self) -> TMat3<T> {
+        let (x, y, z, w, one, two) = (self.x, self.y, self.z, self.w, T::ONE, T::TWO);
+        TMat3::new(one - two * (y * y + z * z), two * (x * y - w * z), two * (x * z + w * y),
+                   two * (x * y + w * z), one - two * (x * x + z * z), two * (y * z - w * x),
+                   two * (x * z - w * y), two * (y * z + w * x), one - two * (x * x + y * y))
+    }
+    pub fn to_mat4(&self) -> TMat4<T> {
+        let m = self.to_mat3();
+        TMat4::from_fn(|r, c| if r < 3 && c < 3 { m[(r, c)] }
+            else if r == c { T::ONE } else { T::ZERO })
+    }
+}
+
+/// Hamilton product: `lhs * rhs` rotates by `rhs` first, then `lhs`.
+impl<T: Scalar + 'static> Mul for Quat<T> {     type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let (a, b) = (self.vec(), rhs.vec());   let c = cross3(&a, &b);
+        Self::new(self.w * b.x + rhs.w * a.x + c.x, self.w * b.y + rhs.w * a.y + c.y,
+                  self.w * b.z + rhs.w * a.z + c.z, self.w * rhs.w - dot3(&a, &b))
+    }
+}
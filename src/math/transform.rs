@@ -0,0 +1,194 @@
+
+use core::ops::Mul;
+use crate::{geometry::{Point, Vector}, math::Scalar};
+
+/** 2D affine transformation in the form of SVG `matrix(a b c d e f)`,
+    `pre_*` applies the operation before the current transformation, and `post_*` after it.
+```text
+    | a c e |   | x |       x' = a x + c y + e
+    | b d f | x | y |  =>   y' = b x + d y + f
+    | 0 0 1 |   | 1 |
+```
+
+```
+    use ugl_rs::{geometry::Point, math::Transform2D};
+    use core::f32::consts::FRAC_PI_2;
+
+    let near = |a: Point, b: Point| (a - b).length() < 1e-5;
+    let ts = Transform2D::translation(10., 20.).pre_rotate(FRAC_PI_2).pre_scale(2., 3.);
+    assert!(near(ts.map_point(Point::new(1., 1.)), Point::new(7., 22.)));
+    assert!(near(ts.map_vector(Point::new(1., 1.)), Point::new(-3., 2.)));
+    assert!(near(ts.invert().unwrap().map_point(Point::new(7., 22.)), Point::new(1., 1.)));
+    assert_eq!(ts, Transform2D::scaling(2., 3.).post_rotate(FRAC_PI_2).post_translate(10., 20.));
+
+    let ts = Transform2D::translation(10., 20.).pre_rotate(FRAC_PI_2)
+        .pre_skew(0.5, 0.).pre_scale(2., 3.);
+    let dc = ts.decompose().unwrap();
+    assert!((dc.rotate - FRAC_PI_2).abs() < 1e-5 && (dc.skew - 0.5).abs() < 1e-5);
+    assert!((dc.scale.x - 2.).abs() < 1e-5 && (dc.scale.y - 3.).abs() < 1e-5);
+    assert_eq!(dc.translate, Point::new(10., 20.));
+    let rc = Transform2D::from(dc);
+    assert!(near(rc.map_point(Point::new(5., 7.)), ts.map_point(Point::new(5., 7.))));
+
+    assert_eq!(Transform2D::scaling(0., 1.).invert(), None);
+    assert_eq!(Transform2D::scaling(-1., 1.).decompose().unwrap().scale, Point::new(1., -1.));
+``` */
+#[derive(Clone, Copy, Debug, PartialEq)] pub struct Transform2D<T: Scalar = f32> {
+    pub a: T, pub b: T, pub c: T, pub d: T, pub e: T, pub f: T,
+}
+
+/// Decomposed components of a transformation, to be applied in order of
+/// scale, skew (along x-axis), rotate and translate.
+#[derive(Clone, Copy, Debug, PartialEq)] pub struct Decomposed<T: Scalar = f32> {
+    pub translate: Vector<T>, pub scale: Vector<T>,
+    /// rotation angle in radians
+    pub rotate: T,
+    /// skew angle along x-axis in radians
+    pub skew: T,
+}
+
+impl<T: Scalar> Default for Transform2D<T> { #[inline] fn default() -> Self { Self::identity() } }
+
+impl<T: Scalar> Transform2D<T> {
+    #[inline] pub fn new(a: T, b: T, c: T, d: T, e: T, f: T) -> Self { Self { a, b, c, d, e, f } }
+    #[inline] pub fn identity() -> Self {
+        Self::new(T::ONE, T::ZERO, T::ZERO, T::ONE, T::ZERO, T::ZERO)
+    }
+
+    #[inline] pub fn translation(tx: T, ty: T) -> Self {
+        Self::new(T::ONE, T::ZERO, T::ZERO, T::ONE, tx, ty)
+    }
+    #[inline] pub fn scaling(sx: T, sy: T) -> Self {
+        Self::new(sx, T::ZERO, T::ZERO, sy, T::ZERO, T::ZERO)
+    }
+    /// Rotate by the angle in radians, clockwise in y-down coordinates.
+    #[inline] pub fn rotation(angle: T) -> Self {
+        let (sin, cos) = angle.sin_cos();   Self::new(cos, sin, -sin, cos, T::ZERO, T::ZERO)
+    }
+    /// Skew by the angles in radians along x-axis and y-axis, as SVG skewX/skewY.
+    #[inline] pub fn skewing(ax: T, ay: T) -> Self {
+        let tan = |angle: T| { let (sin, cos) = angle.sin_cos(); sin / cos };
+        Self::new(T::ONE, tan(ay), tan(ax), T::ONE, T::ZERO, T::ZERO)
+    }
+
+    #[inline] pub fn is_identity(&self) -> bool { *self == Self::identity() }
+    #[inline] pub fn is_translate_only(&self) -> bool {
+        self.a == T::ONE && self.b == T::ZERO && self.c == T::ZERO && self.d == T::ONE
+    }
+    #[inline] pub fn determinant(&self) -> T { self.a * self.d - self.b * self.c }
+
+    #[inline] pub fn pre_concat (&self, other: &Self) -> Self { *self * *other }
+    #[inline] pub fn post_concat(&self, other: &Self) -> Self { *other * *self }
+
+    #[inline] pub fn pre_translate (&self, tx: T, ty: T) -> Self {
+        self.pre_concat (&Self::translation(tx, ty))
+    }
+    #[inline] pub fn post_translate(&self, tx: T, ty: T) -> Self {
+        let mut ts = *self;     ts.e += tx;     ts.f += ty;     ts
+    }
+    #[inline] pub fn pre_scale (&self, sx: T, sy: T) -> Self {
+        self.pre_concat (&Self::scaling(sx, sy))
+    }
+    #[inline] pub fn post_scale(&self, sx: T, sy: T) -> Self {
+        self.post_concat(&Self::scaling(sx, sy))
+    }
+    #[inline] pub fn pre_rotate (&self, angle: T) -> Self {
+        self.pre_concat (&Self::rotation(angle))
+    }
+    #[inline] pub fn post_rotate(&self, angle: T) -> Self {
+        self.post_concat(&Self::rotation(angle))
+    }
+    #[inline] pub fn pre_skew (&self, ax: T, ay: T) -> Self {
+        self.pre_concat (&Self::skewing(ax, ay))
+    }
+    #[inline] pub fn post_skew(&self, ax: T, ay: T) -> Self {
+        self.post_concat(&Self::skewing(ax, ay))
+    }
+
+    /// The inverse transformation, `None` if it's singular (|determinant| <= EPSILON).
+    pub fn invert(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det.abs() <= T::EPSILON { return None }
+
+        let (a, b, c, d) = (self.d / det, -self.b / det, -self.c / det, self.a / det);
+        Some(Self::new(a, b, c, d, -(a * self.e + c * self.f), -(b * self.e + d * self.f)))
+    }
+
+    #[inline] pub fn map_point (&self, pt: Point<T>) -> Point<T> {
+        Point::new(self.a * pt.x + self.c * pt.y + self.e, self.b * pt.x + self.d * pt.y + self.f)
+    }
+    /// Map a vector without translation.
+    #[inline] pub fn map_vector(&self, vec: Vector<T>) -> Vector<T> {
+        Point::new(self.a * vec.x + self.c * vec.y, self.b * vec.x + self.d * vec.y)
+    }
+    pub fn map_points(&self, pts: &mut [Point<T>]) {
+        pts.iter_mut().for_each(|pt| *pt = self.map_point(*pt))
+    }
+    /// Map corners of the axis-aligned rectangle (by its min and max corners),
+    /// in order of (min.x, min.y), (max.x, min.y), (max.x, max.y), (min.x, max.y).
+    pub fn map_rect(&self, min: Point<T>, max: Point<T>) -> [Point<T>; 4] {
+        [min, Point::new(max.x, min.y), max, Point::new(min.x, max.y)].map(|pt| self.map_point(pt))
+    }
+
+    /// Decompose into translate, rotate, skew and scale by QR decomposition
+    /// (negative determinant results in negative y scale), `None` if it's singular.
+    pub fn decompose(&self) -> Option<Decomposed<T>> {
+        let sx = Vector::new(self.a, self.b).length();
+        if sx <= T::EPSILON { return None }
+
+        let (ux, uy) = (self.a / sx, self.b / sx);
+        let sy = ux * self.d - uy * self.c;     // signed, as cross product
+        if sy.abs() <= T::EPSILON { return None }
+        let shear = (ux * self.c + uy * self.d) / sy;
+
+        Some(Decomposed { translate: Vector::new(self.e, self.f), scale: Vector::new(sx, sy),
+            rotate: uy.atan2(ux), skew: shear.atan2(T::ONE) })
+    }
+
+    /** Interpolation to the other at `t` by the decomposed components, with the rotation
+        in the shorter direction, or by the matrix elements if either is singular.
+    ```
+        use ugl_rs::{geometry::Point, math::Transform2D};
+        use core::f32::consts::FRAC_PI_2;
+        let a = Transform2D::translation(10., 0.).pre_rotate(-FRAC_PI_2 * 1.5);
+        let b = Transform2D::translation(20., 10.).pre_rotate( FRAC_PI_2 * 1.5).pre_scale(3., 3.);
+        let dc = a.lerp(&b, 0.5).decompose().unwrap();  // through the angle of PI
+        assert!((dc.rotate.abs() - FRAC_PI_2 * 2.).abs() < 1e-5);
+        assert!((dc.scale - Point::new(2., 2.)).length() < 1e-5);
+        assert_eq!(dc.translate, Point::new(15., 5.));
+        assert_eq!(a.lerp(&b, 0.), a);
+        let zero = Transform2D::scaling(0., 0.);
+        assert_eq!(zero.lerp(&Transform2D::identity(), 0.5), Transform2D::scaling(0.5, 0.5));
+    ``` */
+    pub fn lerp(&self, other: &Self, t: T) -> Self {
+        let (Some(a), Some(b)) = (self.decompose(), other.decompose()) else {
+            return Self::new(self.a.lerp(other.a, t), self.b.lerp(other.b, t),
+                self.c.lerp(other.c, t), self.d.lerp(other.d, t),
+                self.e.lerp(other.e, t), self.f.lerp(other.f, t))
+        };
+        if t == T::ZERO { return *self } else if t == T::ONE { return *other }     // exactly
+
+        let (pi, tau) = (T::from_f64(core::f64::consts::PI), T::from_f64(core::f64::consts::TAU));
+        let mut delta = b.rotate - a.rotate;
+        if pi < delta { delta -= tau } else if delta < -pi { delta += tau }
+        Decomposed { translate: a.translate.lerp(b.translate, t), scale: a.scale.lerp(b.scale, t),
+            rotate: a.rotate + delta * t, skew: a.skew.lerp(b.skew, t) }.into()
+    }
+}
+
+impl<T: Scalar> From<Decomposed<T>> for Transform2D<T> {
+    fn from(dc: Decomposed<T>) -> Self {
+        Self::translation(dc.translate.x, dc.translate.y).pre_rotate(dc.rotate)
+            .pre_skew(dc.skew, T::ZERO).pre_scale(dc.scale.x, dc.scale.y)
+    }
+}
+
+/// Matrix multiplication: `lhs * rhs` applies `rhs` first, then `lhs`.
+impl<T: Scalar> Mul for Transform2D<T> {    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(self.a * rhs.a + self.c * rhs.b, self.b * rhs.a + self.d * rhs.b,
+                  self.a * rhs.c + self.c * rhs.d, self.b * rhs.c + self.d * rhs.d,
+                  self.a * rhs.e + self.c * rhs.f + self.e,
+                  self.b * rhs.e + self.d * rhs.f + self.f)
+    }
+}
@@ -1,3 +1,173 @@
 
 //  3D shaders: Vertex Shader and Pixel/Fragment Shader
 
+use crate::blend::{mul255, SpanFn};
+use crate::color::RGBA;
+
+/// Blend factors to scale the source/destination color, as glBlendFuncSeparate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)] pub enum BlendFactor {
+    Zero, One,
+    SrcColor, OneMinusSrcColor, DstColor, OneMinusDstColor,
+    SrcAlpha, OneMinusSrcAlpha, DstAlpha, OneMinusDstAlpha,
+    ConstantColor, OneMinusConstantColor, ConstantAlpha, OneMinusConstantAlpha,
+    /** min(αs, 1 - αd) for RGB, 1 for alpha */ SrcAlphaSaturate,
+}
+
+/// Blend equations to combine the scaled source and destination, as glBlendEquationSeparate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)] pub enum BlendEquation {
+    /** S x Fs + D x Fd */ Add, /** S x Fs - D x Fd */ Subtract,
+    /** D x Fd - S x Fs */ ReverseSubtract,
+    /** min(S, D), factors ignored */ Min, /** max(S, D), factors ignored */ Max,
+}
+
+/** OpenGL-style configurable blend state for the 3D pipeline,
+    results are clamped to the range of color channel, refer to
+    https://registry.khronos.org/OpenGL-Refpages/gl4/html/glBlendFuncSeparate.xhtml
+```
+    use ugl_rs::{color::RGBA, shader::*};
+    let (src, dst) = (RGBA::new(255, 0, 0, 128), RGBA::new(0, 0, 255, 255));
+    assert_eq!(BlendState::default().blend(src, dst), src);
+    assert_eq!(BlendState::alpha_blending().blend(src, dst), RGBA::new(128, 0, 127, 255));
+
+    let mut state = BlendState::additive();
+    state.write_mask = [true, true, true, false];
+    assert_eq!(state.blend(src, dst), RGBA::new(255, 0, 255, 255));
+
+    state.eq_rgb = BlendEquation::Min;
+    assert_eq!(state.blend(src, dst), RGBA::new(0, 0, 0, 255));
+
+    let mut span = [dst; 3];    state.compile()(&mut span, &[src; 3]);
+    assert_eq!(span, [state.blend(src, dst); 3]);
+    let mut span = [dst; 3];    BlendState::default().compile()(&mut span, &[src; 2]);
+    assert_eq!(span, [src, src, dst]);  // the shorter of both
+``` */
+#[derive(Clone, Copy, Debug, PartialEq)] pub struct BlendState {
+    /// the source color overwrites the destination if disabled
+    pub enabled: bool,
+    pub src_rgb: BlendFactor, pub dst_rgb: BlendFactor, pub eq_rgb: BlendEquation,
+    pub src_alpha: BlendFactor, pub dst_alpha: BlendFactor, pub eq_alpha: BlendEquation,
+    /// the constant color for BlendFactor::Constant*
+    pub constant: RGBA<u8>,
+    /// channels (r, g, b, a) to be written
+    pub write_mask: [bool; 4],
+}
+
+impl Default for BlendState {   // the initial state of OpenGL
+    fn default() -> Self {
+        Self { enabled: false, constant: RGBA::zeroed(), write_mask: [true; 4],
+            src_rgb:   BlendFactor::One, dst_rgb:   BlendFactor::Zero, eq_rgb: BlendEquation::Add,
+            src_alpha: BlendFactor::One, dst_alpha: BlendFactor::Zero,
+            eq_alpha: BlendEquation::Add }
+    }
+}
+
+impl BlendState {
+    /// Set the same factors for both RGB and alpha, as glBlendFunc.
+    pub fn with_func(src: BlendFactor, dst: BlendFactor) -> Self {
+        Self { enabled: true, src_rgb: src, dst_rgb: dst, src_alpha: src, dst_alpha: dst,
+            ..Default::default() }
+    }
+
+    /// Source-over for NON-premultiplied color.
+    #[inline] pub fn alpha_blending() -> Self {
+        let mut state = Self::with_func(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha);
+        state.src_alpha = BlendFactor::One;     state
+    }
+    /// Source-over for pre-multiplied color.
+    #[inline] pub fn premultiplied() -> Self {
+        Self::with_func(BlendFactor::One, BlendFactor::OneMinusSrcAlpha)
+    }
+    #[inline] pub fn additive() -> Self { Self::with_func(BlendFactor::One, BlendFactor::One) }
+    #[inline] pub fn multiply() -> Self { Self::with_func(BlendFactor::DstColor, BlendFactor::Zero) }
+
+    /// Blend a pixel of the source/fragment onto the destination/framebuffer.
+    pub fn blend(&self, src: RGBA<u8>, dst: RGBA<u8>) -> RGBA<u8> {
+        Resolved::new(self).blend(src, dst)
+    }
+
+    /// Compile the state into a span compositor, with the factors and equations resolved
+    /// once rather than per pixel.
+    pub fn compile(self) -> SpanFn {
+        if !self.enabled && self.write_mask == [true; 4] {
+            return Box::new(|dst, src| {
+                let n = dst.len().min(src.len());   dst[..n].copy_from_slice(&src[..n])
+            })
+        }
+        if self.write_mask == [false; 4] { return Box::new(|_, _| ()) }
+
+        let state = Resolved::new(&self);
+        Box::new(move |dst, src| dst.iter_mut().zip(src).for_each(|(d, s)|
+            *d = state.blend(*s, *d)))
+    }
+}
+
+/// Factor of (r, g, b, a) in the range of [0, 255], from the source, destination and
+/// constant colors.
+type FactorFn = fn(RGBA<u8>, RGBA<u8>, RGBA<u8>) -> [u32; 4];
+/// Equation of a channel from the source and destination with their factors.
+type EquationFn = fn(u32, u32, u32, u32) -> u32;
+
+/// Blend state with the factors and equations resolved into functions.
+#[derive(Clone, Copy)] struct Resolved {
+    enabled: bool,
+    src_rgb: FactorFn, dst_rgb: FactorFn, eq_rgb: EquationFn,
+    src_alpha: FactorFn, dst_alpha: FactorFn, eq_alpha: EquationFn,
+    constant: RGBA<u8>, write_mask: [bool; 4],
+}
+
+impl Resolved {
+    fn new(state: &BlendState) -> Self {
+        Self { enabled: state.enabled, constant: state.constant, write_mask: state.write_mask,
+            src_rgb: factor(state.src_rgb, false), dst_rgb: factor(state.dst_rgb, false),
+            src_alpha: factor(state.src_alpha, true), dst_alpha: factor(state.dst_alpha, true),
+            eq_rgb: equation(state.eq_rgb), eq_alpha: equation(state.eq_alpha) }
+    }
+
+    fn blend(&self, src: RGBA<u8>, dst: RGBA<u8>) -> RGBA<u8> {
+        let res = if self.enabled {
+            let cc = self.constant;
+            let op = |eq: EquationFn, s: u8, fs: u32, d: u8, fd: u32|
+                eq(s as u32, fs, d as u32, fd) as u8;
+            let (fs, fd) = ((self.src_rgb)(src, dst, cc), (self.dst_rgb)(src, dst, cc));
+            let (fsa, fda) = ((self.src_alpha)(src, dst, cc)[3],
+                              (self.dst_alpha)(src, dst, cc)[3]);
+            RGBA {  r: op(self.eq_rgb, src.r, fs[0], dst.r, fd[0]),
+                    g: op(self.eq_rgb, src.g, fs[1], dst.g, fd[1]),
+                    b: op(self.eq_rgb, src.b, fs[2], dst.b, fd[2]),
+                    a: op(self.eq_alpha, src.a, fsa, dst.a, fda) }
+        } else { src };
+
+        let [r, g, b, a] = self.write_mask;
+        RGBA {  r: if r { res.r } else { dst.r }, g: if g { res.g } else { dst.g },
+                b: if b { res.b } else { dst.b }, a: if a { res.a } else { dst.a } }
+    }
+}
+
+fn factor(factor: BlendFactor, alpha: bool) -> FactorFn {
+    use BlendFactor::*;
+    fn inv([r, g, b, a]: [u32; 4]) -> [u32; 4] { [255 - r, 255 - g, 255 - b, 255 - a] }
+    fn rgba(c: RGBA<u8>) -> [u32; 4] { [c.r as u32, c.g as u32, c.b as u32, c.a as u32] }
+    fn all(v: u8) -> [u32; 4] { [v as u32; 4] }
+
+    match factor {
+        Zero => |_, _, _| [0; 4], One => |_, _, _| [255; 4],
+        SrcColor => |s, _, _| rgba(s),  OneMinusSrcColor => |s, _, _| inv(rgba(s)),
+        DstColor => |_, d, _| rgba(d),  OneMinusDstColor => |_, d, _| inv(rgba(d)),
+        SrcAlpha => |s, _, _| all(s.a), OneMinusSrcAlpha => |s, _, _| inv(all(s.a)),
+        DstAlpha => |_, d, _| all(d.a), OneMinusDstAlpha => |_, d, _| inv(all(d.a)),
+        ConstantColor => |_, _, c| rgba(c), OneMinusConstantColor => |_, _, c| inv(rgba(c)),
+        ConstantAlpha => |_, _, c| all(c.a), OneMinusConstantAlpha => |_, _, c| inv(all(c.a)),
+        SrcAlphaSaturate => if alpha { |_, _, _| [255; 4] } else {
+            |s, d, _| all(s.a.min(255 - d.a)) },
+    }
+}
+
+fn equation(eq: BlendEquation) -> EquationFn {
+    match eq {
+        BlendEquation::Add => |s, fs, d, fd| (mul255(s, fs) + mul255(d, fd)).min(255),
+        BlendEquation::Subtract => |s, fs, d, fd| mul255(s, fs).saturating_sub(mul255(d, fd)),
+        BlendEquation::ReverseSubtract =>
+            |s, fs, d, fd| mul255(d, fd).saturating_sub(mul255(s, fs)),
+        BlendEquation::Min => |s, _, d, _| s.min(d), BlendEquation::Max => |s, _, d, _| s.max(d),
+    }
+}